@@ -0,0 +1,110 @@
+//! Mock SPL Governance program for testing [yi](https://docs.rs/yi) voter weight registrars.
+//!
+//! Realms and token owner records are written verbatim by the caller, so that tests
+//! control every field yi reads from them.
+#![deny(rustdoc::all)]
+#![allow(rustdoc::missing_doc_code_examples)]
+#![deny(clippy::unwrap_used)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, System};
+
+declare_id!("ZJ1LZAZjZc3tvLckZwvUJo6j4Gx14SPEmhAE1PxG1Fy");
+
+/// The [mock_governance] program.
+#[program]
+pub mod mock_governance {
+    use super::*;
+
+    /// Creates a token owner record at its SPL Governance address, holding `space` zeroed bytes.
+    pub fn create_token_owner_record(
+        ctx: Context<CreateTokenOwnerRecord>,
+        space: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_token_owner_record(*unwrap_bump(ctx.bumps.get("token_owner_record"))?, space)
+    }
+
+    /// Overwrites the start of an account owned by this program with `data`.
+    pub fn write(ctx: Context<Write>, data: Vec<u8>) -> Result<()> {
+        let mut account_data = ctx.accounts.account.try_borrow_mut_data()?;
+        account_data
+            .get_mut(..data.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?
+            .copy_from_slice(&data);
+        Ok(())
+    }
+}
+
+fn unwrap_bump(bump: Option<&u8>) -> Result<&u8> {
+    bump.ok_or_else(|| ProgramError::InvalidSeeds.into())
+}
+
+/// Accounts for [mock_governance::create_token_owner_record].
+#[derive(Accounts)]
+pub struct CreateTokenOwnerRecord<'info> {
+    /// CHECK: The realm.
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: The governing token mint.
+    pub governing_token_mint: UncheckedAccount<'info>,
+    /// CHECK: The governing token owner.
+    pub governing_token_owner: UncheckedAccount<'info>,
+
+    /// CHECK: The token owner record to be created.
+    #[account(
+        mut,
+        seeds = [
+            b"governance".as_ref(),
+            realm.key().as_ref(),
+            governing_token_mint.key().as_ref(),
+            governing_token_owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub token_owner_record: UncheckedAccount<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTokenOwnerRecord<'info> {
+    fn create_token_owner_record(&self, bump: u8, space: u64) -> Result<()> {
+        let realm = self.realm.key();
+        let governing_token_mint = self.governing_token_mint.key();
+        let governing_token_owner = self.governing_token_owner.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"governance",
+            realm.as_ref(),
+            governing_token_mint.as_ref(),
+            governing_token_owner.as_ref(),
+            &[bump],
+        ]];
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.payer.to_account_info(),
+                    to: self.token_owner_record.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            Rent::get()?.minimum_balance(
+                usize::try_from(space).map_err(|_| ProgramError::InvalidArgument)?,
+            ),
+            space,
+            &crate::ID,
+        )
+    }
+}
+
+/// Accounts for [mock_governance::write].
+#[derive(Accounts)]
+pub struct Write<'info> {
+    /// CHECK: The account to write to. Only accounts owned by this program are writable.
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+}
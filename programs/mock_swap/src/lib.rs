@@ -0,0 +1,85 @@
+//! Stand-in swap program for testing [yi](https://docs.rs/yi) swap routes.
+//!
+//! Swaps at a fixed price of two input tokens per output token, paying out of a reserve.
+#![deny(rustdoc::all)]
+#![allow(rustdoc::missing_doc_code_examples)]
+#![deny(clippy::unwrap_used)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+declare_id!("8p658PNUSea7Qpq3tGpWtNS5Lgb11PvSwQU82rRvX8ae");
+
+/// The [mock_swap] program.
+#[program]
+pub mod mock_swap {
+    use super::*;
+
+    /// Swaps `amount_in` input tokens for half as many output tokens.
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let amount_out = amount_in / 2;
+        if amount_out < min_amount_out {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.input_reserve.to_account_info(),
+                    authority: ctx.accounts.source_authority.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+
+        let bump = *ctx
+            .bumps
+            .get("reserve_authority")
+            .ok_or(ProgramError::InvalidSeeds)?;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"Reserve", &[bump]]];
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.output_reserve.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.reserve_authority.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount_out,
+        )
+    }
+}
+
+/// Accounts for [mock_swap::swap].
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    /// Input tokens to swap.
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    /// Owner of [Self::source].
+    pub source_authority: Signer<'info>,
+    /// Tokens receiving the output.
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Reserve receiving input tokens.
+    #[account(mut, constraint = input_reserve.mint == source.mint)]
+    pub input_reserve: Account<'info, TokenAccount>,
+    /// Reserve paying out output tokens.
+    #[account(
+        mut,
+        constraint = output_reserve.mint == destination.mint,
+        constraint = output_reserve.owner == reserve_authority.key()
+    )]
+    pub output_reserve: Account<'info, TokenAccount>,
+    /// CHECK: Owner of the reserves.
+    #[account(seeds = [b"Reserve"], bump)]
+    pub reserve_authority: UncheckedAccount<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,123 @@
+//! Mock yield source for testing [yi](https://docs.rs/yi) harvest adapters and strategies.
+//!
+//! Tokens sent to the reserve are paid out in full on each harvest.
+//! As a strategy, deposited tokens simply sit in the strategy reserve of the yiToken, owned by the
+//! PDA `["StrategyAuthority", yi_token]`, until they are withdrawn.
+#![deny(rustdoc::all)]
+#![allow(rustdoc::missing_doc_code_examples)]
+#![deny(clippy::unwrap_used)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+declare_id!("CBTUQ3J4v2BDaHsRSs7DWqb4gfmEx5uzx2tHXQ8KDRtM");
+
+/// The [mock_yield] program.
+#[program]
+pub mod mock_yield {
+    use super::*;
+
+    /// Pays out the entire reserve, reporting the amount via return data.
+    pub fn harvest(ctx: Context<Payout>) -> Result<()> {
+        let amount = ctx.accounts.reserve_tokens.amount;
+        ctx.accounts
+            .pay_out(*unwrap_bump(ctx.bumps.get("reserve_authority"))?, amount)?;
+        set_return_data(&amount.to_le_bytes());
+        Ok(())
+    }
+
+    /// Accepts tokens deposited into the reserve by a strategy.
+    pub fn deposit(_ctx: Context<Deposit>, _amount: u64) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns `amount` tokens from the strategy reserve to a strategy.
+    pub fn withdraw(ctx: Context<StrategyWithdraw>, amount: u64) -> Result<()> {
+        let bump = *unwrap_bump(ctx.bumps.get("strategy_authority"))?;
+        let yi_token = ctx.accounts.yi_token.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[b"StrategyAuthority", yi_token.as_ref(), &[bump]]];
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.reserve_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.strategy_authority.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+fn unwrap_bump(bump: Option<&u8>) -> Result<&u8> {
+    bump.ok_or_else(|| ProgramError::InvalidSeeds.into())
+}
+
+/// Accounts for [mock_yield::harvest].
+#[derive(Accounts)]
+pub struct Payout<'info> {
+    /// Tokens receiving the payout.
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// Tokens paid out.
+    #[account(mut, constraint = reserve_tokens.owner == reserve_authority.key())]
+    pub reserve_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Owner of the reserve.
+    #[account(seeds = [b"Reserve"], bump)]
+    pub reserve_authority: UncheckedAccount<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Payout<'info> {
+    fn pay_out(&self, bump: u8, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[b"Reserve", &[bump]]];
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_tokens.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.reserve_authority.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+/// Accounts for [mock_yield::deposit].
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// The reserve, which has received the deposited tokens.
+    pub reserve_tokens: Account<'info, TokenAccount>,
+}
+
+/// Accounts for [mock_yield::withdraw].
+#[derive(Accounts)]
+pub struct StrategyWithdraw<'info> {
+    /// Tokens receiving the withdrawal.
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// The strategy reserve.
+    #[account(mut, constraint = reserve_tokens.owner == strategy_authority.key())]
+    pub reserve_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: The yiToken whose tokens are deployed. Only used as a seed.
+    pub yi_token: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the strategy reserve.
+    #[account(seeds = [b"StrategyAuthority", yi_token.key().as_ref()], bump)]
+    pub strategy_authority: UncheckedAccount<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,96 @@
+//! Processor for [yi::batch_stake].
+
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::batch_stake].
+///
+/// The remaining accounts are a (source underlying tokens, destination [YiToken]s) pair
+/// per amount, optionally followed by the [RateProvider] of the [YiToken] to refresh it.
+#[derive(Accounts)]
+pub struct BatchStake<'info> {
+    /// The [YiToken] to stake tokens into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [TokenAccount::owner] of every source [TokenAccount].
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Splits the remaining accounts of a batch into one account pair per amount
+/// and the accounts that follow.
+pub(crate) fn split_batch_accounts<'a, 'info>(
+    amounts: &[u64],
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let num_pair_accounts = unwrap_int!(amounts.len().checked_mul(2));
+    invariant!(
+        remaining_accounts.len() >= num_pair_accounts,
+        BatchAccountsMismatch
+    );
+    Ok(remaining_accounts.split_at(num_pair_accounts))
+}
+
+impl<'info> BatchStake<'info> {
+    fn stake_accounts(
+        &self,
+        source_tokens: Account<'info, TokenAccount>,
+        destination_yi_tokens: Account<'info, TokenAccount>,
+    ) -> Stake<'info> {
+        Stake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_tokens,
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_yi_tokens: Box::new(destination_yi_tokens),
+            token_program: self.token_program.clone(),
+        }
+    }
+
+    fn batch_stake(&mut self, amounts: &[u64], pairs: &[AccountInfo<'info>]) -> Result<()> {
+        for (amount, pair) in amounts.iter().zip(pairs.chunks_exact(2)) {
+            let stake =
+                self.stake_accounts(Account::try_from(&pair[0])?, Account::try_from(&pair[1])?);
+            stake.validate()?;
+            let underlying_amount = stake.resolve_amount(*amount);
+            if underlying_amount == 0 {
+                continue;
+            }
+            stake.stake(underlying_amount)?;
+            // the next stake must convert against the new supply
+            self.yi_mint.reload()?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchStake<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let (pairs, rest) = split_batch_accounts(&amounts, ctx.remaining_accounts)?;
+    ctx.accounts.batch_stake(&amounts, pairs)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, rest)
+}
+
+impl<'info> Validate<'info> for BatchStake<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        Ok(())
+    }
+}
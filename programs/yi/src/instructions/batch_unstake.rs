@@ -0,0 +1,83 @@
+//! Processor for [yi::batch_unstake].
+
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::batch_unstake].
+///
+/// The remaining accounts are a (source [YiToken]s, destination underlying tokens) pair
+/// per amount, optionally followed by the [RateProvider] of the [YiToken] to refresh it.
+#[derive(Accounts)]
+pub struct BatchUnstake<'info> {
+    /// The [YiToken] to unstake tokens from.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [TokenAccount::owner] of every source [TokenAccount].
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BatchUnstake<'info> {
+    fn unstake_accounts(
+        &self,
+        source_yi_tokens: Account<'info, TokenAccount>,
+        destination_underlying_tokens: Account<'info, TokenAccount>,
+    ) -> Unstake<'info> {
+        Unstake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_yi_tokens,
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_underlying_tokens: Box::new(destination_underlying_tokens),
+            token_program: self.token_program.clone(),
+        }
+    }
+
+    fn batch_unstake(&mut self, amounts: &[u64], pairs: &[AccountInfo<'info>]) -> Result<()> {
+        for (amount, pair) in amounts.iter().zip(pairs.chunks_exact(2)) {
+            let unstake =
+                self.unstake_accounts(Account::try_from(&pair[0])?, Account::try_from(&pair[1])?);
+            unstake.validate()?;
+            let yitoken_amount = unstake.resolve_amount(*amount);
+            if yitoken_amount == 0 {
+                continue;
+            }
+            unstake.unstake(yitoken_amount)?;
+            // the next unstake must convert against the new supply and liquidity
+            self.yi_mint.reload()?;
+            self.yi_underlying_tokens.reload()?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BatchUnstake<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let (pairs, rest) = split_batch_accounts(&amounts, ctx.remaining_accounts)?;
+    ctx.accounts.batch_unstake(&amounts, pairs)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, rest)
+}
+
+impl<'info> Validate<'info> for BatchUnstake<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        Ok(())
+    }
+}
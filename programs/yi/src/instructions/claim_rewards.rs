@@ -0,0 +1,71 @@
+//! Processor for [yi::claim_rewards].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::claim_rewards].
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// The [RewardPosition] to claim the rewards of.
+    pub reward_position: RewardPositionAccounts<'info>,
+
+    /// [RewardStream::reward_tokens].
+    #[account(mut)]
+    pub reward_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [TokenAccount] receiving the rewards.
+    #[account(mut)]
+    pub destination_reward_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimRewards<'info> {
+    fn claim_rewards(&mut self) -> Result<()> {
+        self.reward_position.update_rewards()?;
+
+        // an underfunded stream pays out what it can; the rest remains claimable
+        let position = &mut self.reward_position.position;
+        let amount = position.rewards_earned.min(self.reward_tokens.amount);
+        if amount == 0 {
+            return Ok(());
+        }
+        position.rewards_earned = unwrap_int!(position.rewards_earned.checked_sub(amount));
+
+        let stream = &self.reward_position.stream;
+        let signer_seeds: &[&[&[u8]]] = reward_stream_seeds!(stream);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reward_tokens.to_account_info(),
+                    to: self.destination_reward_tokens.to_account_info(),
+                    authority: stream.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    ctx.accounts.claim_rewards()
+}
+
+impl<'info> Validate<'info> for ClaimRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        self.reward_position.validate()?;
+        assert_keys_eq!(
+            self.reward_position.stream.reward_tokens,
+            self.reward_tokens
+        );
+        assert_keys_eq!(
+            self.destination_reward_tokens.mint,
+            self.reward_position.stream.reward_mint
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! Processor for [yi::compound_from].
+
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::compound_from].
+///
+/// The remaining accounts are passed through to the swap program.
+#[derive(Accounts)]
+pub struct CompoundFrom<'info> {
+    /// The [YiToken] to compound into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Box<Account<'info, Mint>>,
+
+    /// The [SwapRoute].
+    #[account(mut)]
+    pub route: AccountLoader<'info, SwapRoute>,
+
+    /// CHECK: [SwapRoute::swap_program].
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// [SwapRoute::side_vault].
+    #[account(mut)]
+    pub side_vault: Box<Account<'info, TokenAccount>>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// [YiToken::admin]. Only the admin may choose `min_amount_out`, since anyone
+    /// else could sandwich the swap.
+    pub admin: Signer<'info>,
+}
+
+impl<'info> CompoundFrom<'info> {
+    fn compound_from(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        min_amount_out: u64,
+    ) -> Result<()> {
+        let amount_in = self.side_vault.amount;
+        if amount_in == 0 {
+            return Ok(());
+        }
+        let balance_before = self.yi_underlying_tokens.amount;
+
+        let accounts: Vec<AccountInfo<'info>> = [
+            self.side_vault.to_account_info(),
+            self.route.to_account_info(),
+            self.yi_underlying_tokens.to_account_info(),
+        ]
+        .into_iter()
+        .chain(remaining_accounts.iter().cloned())
+        .collect();
+        let mut args = amount_in.to_le_bytes().to_vec();
+        args.extend_from_slice(&min_amount_out.to_le_bytes());
+        // copied so the route is not borrowed during the CPI
+        let route = *self.route.load()?;
+        let signer_seeds: &[&[&[u8]]] = swap_route_seeds!(route);
+        // the route only controls the side vault, so that is all the swap program may take
+        invoke_adapter_signed(
+            &self.swap_program,
+            "swap",
+            &args,
+            &accounts,
+            Some(&self.route.key()),
+            signer_seeds,
+        )?;
+
+        self.yi_underlying_tokens.reload()?;
+        let amount_out = unwrap_int!(self.yi_underlying_tokens.amount.checked_sub(balance_before));
+        invariant!(amount_out >= min_amount_out, SwapOutputTooLow);
+
+        {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            unwrap_int!(yi_token.record_rewards(amount_out));
+            let now = Clock::get()?.unix_timestamp;
+            unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+        }
+        let route = &mut self.route.load_mut()?;
+        route.total_amount_in = unwrap_int!(route.total_amount_in.checked_add(amount_in));
+        route.total_amount_out = unwrap_int!(route.total_amount_out.checked_add(amount_out));
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompoundFrom<'info>>,
+    min_amount_out: u64,
+) -> Result<()> {
+    ctx.accounts
+        .compound_from(ctx.remaining_accounts, min_amount_out)
+}
+
+impl<'info> Validate<'info> for CompoundFrom<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.admin, yi_token.admin);
+        invariant!(self.yi_mint.supply > 0, NoStakedTokens);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+
+        let route = self.route.load()?;
+        assert_keys_eq!(route.yi_token, self.yi_token);
+        assert_keys_eq!(route.swap_program, self.swap_program);
+        assert_keys_eq!(route.side_vault, self.side_vault);
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Processor for [yi::crank_emissions].
+
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::crank_emissions].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct CrankEmissions<'info> {
+    /// The [YiToken] to compound the emissions into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Box<Account<'info, Mint>>,
+
+    /// The [EmissionSchedule].
+    #[account(mut)]
+    pub schedule: AccountLoader<'info, EmissionSchedule>,
+    /// [EmissionSchedule::reserve_tokens].
+    #[account(mut)]
+    pub reserve_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+    /// Underlying tokens receiving the bounty.
+    #[account(mut)]
+    pub bounty_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CrankEmissions<'info> {
+    /// Emits the underlying tokens accrued since the last crank.
+    pub(crate) fn crank_emissions(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let (amount, bounty) = {
+            let schedule = &mut self.schedule.load_mut()?;
+            let (amount, until) =
+                unwrap_int!(schedule.calculate_emission(now, self.reserve_tokens.amount));
+            schedule.last_crank_ts = until;
+
+            // emissions with nobody staked are skipped, as nobody would earn them
+            if amount == 0 || self.yi_mint.supply == 0 {
+                return Ok(());
+            }
+            schedule.total_emitted = unwrap_int!(schedule.total_emitted.checked_add(amount));
+            (amount, unwrap_int!(schedule.calculate_bounty(amount)))
+        };
+        let compounded = unwrap_int!(amount.checked_sub(bounty));
+
+        {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            unwrap_int!(yi_token.record_rewards(compounded));
+            unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+        }
+
+        self.transfer_from_reserve(&self.yi_underlying_tokens, compounded)?;
+        self.transfer_from_reserve(&self.bounty_tokens, bounty)
+    }
+
+    fn transfer_from_reserve(
+        &self,
+        destination: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let schedule = self.schedule.load()?;
+        let signer_seeds: &[&[&[u8]]] = emission_schedule_seeds!(schedule);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.reserve_tokens.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: self.schedule.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<CrankEmissions>) -> Result<()> {
+    ctx.accounts.crank_emissions()?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for CrankEmissions<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        assert_keys_eq!(self.bounty_tokens.mint, yi_token.underlying_token_mint);
+
+        let schedule = self.schedule.load()?;
+        assert_keys_eq!(schedule.yi_token, self.yi_token);
+        assert_keys_eq!(schedule.reserve_tokens, self.reserve_tokens);
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+//! Processor for [yi::create_emission_schedule].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_emission_schedule].
+#[derive(Accounts)]
+pub struct CreateEmissionSchedule<'info> {
+    /// The [YiToken] to emit into.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [EmissionSchedule] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"EmissionSchedule".as_ref(),
+            yi_token.key().as_ref()
+        ],
+        bump,
+        space = 8 + EmissionSchedule::SIZE,
+        payer = payer
+    )]
+    pub schedule: AccountLoader<'info, EmissionSchedule>,
+
+    /// [EmissionSchedule::reserve_tokens].
+    pub reserve_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Arbitrary account which may change the schedule.
+    pub authority: UncheckedAccount<'info>,
+
+    /// [YiToken::admin]. Only the admin may create the schedule, since each [YiToken]
+    /// has a single [EmissionSchedule].
+    pub admin: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateEmissionSchedule<'info> {
+    fn create_emission_schedule(
+        &mut self,
+        bump: u8,
+        emissions_per_second: u64,
+        end_ts: i64,
+        bounty_millibps: u32,
+    ) -> Result<()> {
+        let schedule = &mut self.schedule.load_init()?;
+        schedule.yi_token = self.yi_token.key();
+        schedule.bump = bump;
+
+        schedule.authority = self.authority.key();
+        schedule.reserve_tokens = self.reserve_tokens.key();
+
+        schedule.emissions_per_second = emissions_per_second;
+        schedule.end_ts = end_ts;
+        schedule.last_crank_ts = Clock::get()?.unix_timestamp;
+        schedule.total_emitted = 0;
+
+        schedule.bounty_millibps = bounty_millibps;
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<CreateEmissionSchedule>,
+    emissions_per_second: u64,
+    end_ts: i64,
+    bounty_millibps: u32,
+) -> Result<()> {
+    invariant!(
+        bounty_millibps <= MAX_EMISSION_BOUNTY_MILLIBPS,
+        InvalidEmissionBounty
+    );
+    ctx.accounts.create_emission_schedule(
+        *unwrap_int!(ctx.bumps.get("schedule")),
+        emissions_per_second,
+        end_ts,
+        bounty_millibps,
+    )
+}
+
+impl<'info> Validate<'info> for CreateEmissionSchedule<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        assert_keys_eq!(self.reserve_tokens.owner, self.schedule);
+        assert_keys_eq!(self.reserve_tokens.mint, yi_token.underlying_token_mint);
+        invariant!(self.reserve_tokens.delegate.is_none());
+        invariant!(self.reserve_tokens.close_authority.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Processor for [yi::create_escrow].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_escrow].
+#[derive(Accounts)]
+pub struct CreateEscrow<'info> {
+    /// [Escrow::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [Escrow] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Escrow".as_ref(),
+            yi_token.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        space = 8 + Escrow::SIZE,
+        payer = payer
+    )]
+    pub escrow: AccountLoader<'info, Escrow>,
+
+    /// [Escrow::tokens].
+    pub escrow_tokens: Account<'info, TokenAccount>,
+
+    /// Owner of the [Escrow].
+    pub owner: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateEscrow<'info> {
+    fn create_escrow(&mut self, bump: u8) -> Result<()> {
+        let escrow = &mut self.escrow.load_init()?;
+        escrow.yi_token = self.yi_token.key();
+        escrow.owner = self.owner.key();
+        escrow.tokens = self.escrow_tokens.key();
+        escrow.bump = bump;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateEscrow>) -> Result<()> {
+    ctx.accounts
+        .create_escrow(*unwrap_int!(ctx.bumps.get("escrow")))
+}
+
+impl<'info> Validate<'info> for CreateEscrow<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_is_zero_token_account!(self.escrow_tokens);
+        assert_keys_eq!(self.escrow_tokens.owner, self.escrow);
+        assert_keys_eq!(self.escrow_tokens.mint, yi_token.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+//! Processor for [yi::create_fee_exemption].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_fee_exemption].
+#[derive(Accounts)]
+#[instruction(exempt_key: Pubkey)]
+pub struct CreateFeeExemption<'info> {
+    /// The [YiToken].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// The [FeeExemption] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"FeeExemption".as_ref(),
+            yi_token.key().as_ref(),
+            exempt_key.as_ref()
+        ],
+        bump,
+        space = 8 + FeeExemption::SIZE,
+        payer = payer
+    )]
+    pub fee_exemption: AccountLoader<'info, FeeExemption>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateFeeExemption>, exempt_key: Pubkey) -> Result<()> {
+    let fee_exemption = &mut ctx.accounts.fee_exemption.load_init()?;
+    fee_exemption.yi_token = ctx.accounts.yi_token.key();
+    fee_exemption.exempt_key = exempt_key;
+    fee_exemption.bump = *unwrap_int!(ctx.bumps.get("fee_exemption"));
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateFeeExemption<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
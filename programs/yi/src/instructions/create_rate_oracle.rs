@@ -0,0 +1,59 @@
+//! Processor for [yi::create_rate_oracle].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_rate_oracle].
+#[derive(Accounts)]
+pub struct CreateRateOracle<'info> {
+    /// The [YiToken] to observe.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [RateOracle] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"RateOracle".as_ref(),
+            yi_token.key().as_ref()
+        ],
+        bump,
+        space = 8 + RateOracle::SIZE,
+        payer = payer
+    )]
+    pub rate_oracle: AccountLoader<'info, RateOracle>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRateOracle<'info> {
+    fn create_rate_oracle(&mut self, bump: u8) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        let rate_oracle = &mut self.rate_oracle.load_init()?;
+        rate_oracle.yi_token = self.yi_token.key();
+        rate_oracle.bump = bump;
+
+        let now = Clock::get()?.unix_timestamp;
+        unwrap_int!(rate_oracle.record(RateObservation {
+            timestamp: now,
+            rate_cumulative: unwrap_int!(yi_token.current_rate_cumulative(now)),
+            ..RateObservation::default()
+        }));
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateRateOracle>) -> Result<()> {
+    ctx.accounts
+        .create_rate_oracle(*unwrap_int!(ctx.bumps.get("rate_oracle")))
+}
+
+impl<'info> Validate<'info> for CreateRateOracle<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
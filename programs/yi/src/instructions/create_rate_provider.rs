@@ -0,0 +1,65 @@
+//! Processor for [yi::create_rate_provider].
+
+use crate::*;
+use anchor_spl::token::Mint;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_rate_provider].
+#[derive(Accounts)]
+pub struct CreateRateProvider<'info> {
+    /// The [YiToken] to provide the rate of.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [RateProvider] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"RateProvider".as_ref(),
+            yi_token.key().as_ref()
+        ],
+        bump,
+        space = 8 + RateProvider::SIZE,
+        payer = payer
+    )]
+    pub rate_provider: AccountLoader<'info, RateProvider>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRateProvider<'info> {
+    fn create_rate_provider(&mut self, bump: u8) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        let rate_provider = &mut self.rate_provider.load_init()?;
+        rate_provider.yi_token = self.yi_token.key();
+        rate_provider.bump = bump;
+
+        let clock = Clock::get()?;
+        rate_provider.refresh(
+            unwrap_int!(yi_token.calculate_exchange_rate(self.yi_mint.supply)),
+            clock.slot,
+            clock.unix_timestamp,
+        );
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateRateProvider>) -> Result<()> {
+    ctx.accounts
+        .create_rate_provider(*unwrap_int!(ctx.bumps.get("rate_provider")))
+}
+
+impl<'info> Validate<'info> for CreateRateProvider<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Processor for [yi::create_referral].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_referral].
+#[derive(Accounts)]
+pub struct CreateReferral<'info> {
+    /// [Referral::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [Referral] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Referral".as_ref(),
+            yi_token.key().as_ref(),
+            referrer.key().as_ref()
+        ],
+        bump,
+        space = 8 + Referral::SIZE,
+        payer = payer
+    )]
+    pub referral: AccountLoader<'info, Referral>,
+
+    /// CHECK: Arbitrary account which becomes [Referral::referrer].
+    pub referrer: UncheckedAccount<'info>,
+
+    /// [YiToken::admin]. Only the admin may approve referrers, since a staker could
+    /// otherwise refer itself through a second account.
+    pub admin: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateReferral<'info> {
+    fn create_referral(&mut self, bump: u8) -> Result<()> {
+        let referral = &mut self.referral.load_init()?;
+        referral.yi_token = self.yi_token.key();
+        referral.referrer = self.referrer.key();
+        referral.bump = bump;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateReferral>) -> Result<()> {
+    ctx.accounts
+        .create_referral(*unwrap_int!(ctx.bumps.get("referral")))
+}
+
+impl<'info> Validate<'info> for CreateReferral<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! Processor for [yi::create_registrar].
+
+use crate::*;
+use anchor_spl::token::Mint;
+use vipers::prelude::*;
+
+/// [GovernanceAccountType](https://github.com/solana-labs/solana-program-library/blob/master/governance/program/src/state/enums.rs)
+/// discriminants of a realm.
+const REALM_ACCOUNT_TYPES: [u8; 2] = [1, 16];
+
+/// The leading fields of an SPL Governance realm, up to and including its authority.
+#[derive(AnchorDeserialize)]
+struct RealmHeader {
+    account_type: u8,
+    community_mint: Pubkey,
+    _use_community_voter_weight_addin: bool,
+    _use_max_community_voter_weight_addin: bool,
+    _config_reserved: [u8; 6],
+    _min_community_weight_to_create_governance: u64,
+    _community_mint_max_vote_weight_source: (u8, u64),
+    council_mint: Option<Pubkey>,
+    _reserved: [u8; 6],
+    _voting_proposal_count: u16,
+    authority: Option<Pubkey>,
+}
+
+/// Accounts for [yi::create_registrar].
+#[derive(Accounts)]
+pub struct CreateRegistrar<'info> {
+    /// [Registrar::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// CHECK: [Registrar::realm]. Checked to be owned by [Self::governance_program].
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: [Registrar::governance_program_id]. Checked to be executable.
+    pub governance_program: UncheckedAccount<'info>,
+    /// [Registrar::governing_token_mint].
+    pub governing_token_mint: Account<'info, Mint>,
+    /// The authority of [Self::realm].
+    pub realm_authority: Signer<'info>,
+
+    /// The [Registrar] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Registrar".as_ref(),
+            realm.key().as_ref(),
+            governing_token_mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + Registrar::SIZE,
+        payer = payer
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRegistrar<'info> {
+    fn create_registrar(&mut self, bump: u8) -> Result<()> {
+        let registrar = &mut self.registrar.load_init()?;
+        registrar.governance_program_id = self.governance_program.key();
+        registrar.realm = self.realm.key();
+        registrar.governing_token_mint = self.governing_token_mint.key();
+        registrar.yi_token = self.yi_token.key();
+        registrar.bump = bump;
+        Ok(())
+    }
+
+    fn load_realm(&self) -> Result<RealmHeader> {
+        let data = self.realm.try_borrow_data()?;
+        let realm = RealmHeader::deserialize(&mut data.as_ref())
+            .map_err(|_| error!(ErrorCode::InvalidRealm))?;
+        invariant!(
+            REALM_ACCOUNT_TYPES.contains(&realm.account_type),
+            InvalidRealm
+        );
+        Ok(realm)
+    }
+}
+
+pub fn handler(ctx: Context<CreateRegistrar>) -> Result<()> {
+    ctx.accounts
+        .create_registrar(*unwrap_int!(ctx.bumps.get("registrar")))
+}
+
+impl<'info> Validate<'info> for CreateRegistrar<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.governance_program.executable);
+        assert_keys_eq!(*self.realm.owner, self.governance_program, InvalidRealm);
+
+        let realm = self.load_realm()?;
+        assert_keys_eq!(
+            unwrap_opt!(realm.authority, InvalidRealm),
+            self.realm_authority
+        );
+        invariant!(
+            realm.community_mint == self.governing_token_mint.key()
+                || realm.council_mint == Some(self.governing_token_mint.key()),
+            InvalidRealm
+        );
+        Ok(())
+    }
+}
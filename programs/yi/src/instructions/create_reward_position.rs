@@ -0,0 +1,75 @@
+//! Processor for [yi::create_reward_position].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_reward_position].
+#[derive(Accounts)]
+pub struct CreateRewardPosition<'info> {
+    /// [RewardStream::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [RewardStream] to earn from.
+    pub stream: Account<'info, RewardStream>,
+
+    /// The [RewardPosition] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"RewardPosition".as_ref(),
+            stream.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        space = 8 + RewardPosition::SIZE,
+        payer = payer
+    )]
+    pub position: Account<'info, RewardPosition>,
+
+    /// [RewardPosition::yi_tokens].
+    pub position_yi_tokens: Account<'info, TokenAccount>,
+
+    /// Owner of the [RewardPosition].
+    pub owner: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRewardPosition<'info> {
+    fn create_reward_position(&mut self, bump: u8) -> Result<()> {
+        let position = &mut self.position;
+        position.stream = self.stream.key();
+        position.owner = self.owner.key();
+        position.bump = bump;
+
+        position.yi_tokens = self.position_yi_tokens.key();
+
+        position.shares = 0;
+        position.reward_per_share_paid = self.stream.reward_per_share;
+        position.rewards_earned = 0;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateRewardPosition>) -> Result<()> {
+    ctx.accounts
+        .create_reward_position(*unwrap_int!(ctx.bumps.get("position")))
+}
+
+impl<'info> Validate<'info> for CreateRewardPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.stream.yi_token, self.yi_token);
+
+        assert_is_zero_token_account!(self.position_yi_tokens);
+        assert_keys_eq!(self.position_yi_tokens.owner, self.position);
+        assert_keys_eq!(self.position_yi_tokens.mint, yi_token.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Processor for [yi::create_reward_stream].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_reward_stream].
+#[derive(Accounts)]
+pub struct CreateRewardStream<'info> {
+    /// The [YiToken] whose holders are rewarded.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [Mint] of the reward token.
+    pub reward_mint: Account<'info, Mint>,
+
+    /// The [RewardStream] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"RewardStream".as_ref(),
+            yi_token.key().as_ref(),
+            reward_mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + RewardStream::SIZE,
+        payer = payer
+    )]
+    pub stream: Account<'info, RewardStream>,
+
+    /// [RewardStream::reward_tokens].
+    pub reward_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Arbitrary account which may set the reward rate.
+    pub authority: UncheckedAccount<'info>,
+
+    /// [YiToken::admin]. Only the admin may create streams, since each reward mint
+    /// has a single [RewardStream] per [YiToken].
+    pub admin: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRewardStream<'info> {
+    fn create_reward_stream(&mut self, bump: u8) -> Result<()> {
+        let stream = &mut self.stream;
+        stream.yi_token = self.yi_token.key();
+        stream.reward_mint = self.reward_mint.key();
+        stream.bump = bump;
+
+        stream.authority = self.authority.key();
+        stream.reward_tokens = self.reward_tokens.key();
+
+        stream.rewards_per_second = 0;
+        stream.total_shares = 0;
+        stream.reward_per_share = 0;
+        stream.last_update_ts = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateRewardStream>) -> Result<()> {
+    ctx.accounts
+        .create_reward_stream(*unwrap_int!(ctx.bumps.get("stream")))
+}
+
+impl<'info> Validate<'info> for CreateRewardStream<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        assert_is_zero_token_account!(self.reward_tokens);
+        assert_keys_eq!(self.reward_tokens.owner, self.stream);
+        assert_keys_eq!(self.reward_tokens.mint, self.reward_mint);
+        Ok(())
+    }
+}
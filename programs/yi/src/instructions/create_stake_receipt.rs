@@ -0,0 +1,57 @@
+//! Processor for [yi::create_stake_receipt].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_stake_receipt].
+#[derive(Accounts)]
+pub struct CreateStakeReceipt<'info> {
+    /// [StakeReceipt::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [StakeReceipt] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"StakeReceipt".as_ref(),
+            yi_token.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        space = 8 + StakeReceipt::SIZE,
+        payer = payer
+    )]
+    pub receipt: AccountLoader<'info, StakeReceipt>,
+
+    /// Owner of the [StakeReceipt].
+    pub owner: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateStakeReceipt<'info> {
+    fn create_stake_receipt(&mut self, bump: u8) -> Result<()> {
+        let receipt = &mut self.receipt.load_init()?;
+        receipt.yi_token = self.yi_token.key();
+        receipt.owner = self.owner.key();
+        receipt.bump = bump;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateStakeReceipt>) -> Result<()> {
+    ctx.accounts
+        .create_stake_receipt(*unwrap_int!(ctx.bumps.get("receipt")))
+}
+
+impl<'info> Validate<'info> for CreateStakeReceipt<'info> {
+    fn validate(&self) -> Result<()> {
+        self.yi_token.load()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! Processor for [yi::create_term].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_term].
+#[derive(Accounts)]
+#[instruction(maturity_ts: i64)]
+pub struct CreateTerm<'info> {
+    /// The [YiToken] to split.
+    pub yi_token: AccountLoader<'info, YiToken>,
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [Term] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Term".as_ref(),
+            yi_token.key().as_ref(),
+            maturity_ts.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + Term::SIZE,
+        payer = payer
+    )]
+    pub term: AccountLoader<'info, Term>,
+
+    /// [Term::principal_mint].
+    pub principal_mint: Account<'info, Mint>,
+    /// [Term::yield_mint].
+    pub yield_mint: Account<'info, Mint>,
+    /// [Term::yi_tokens].
+    pub term_yi_tokens: Account<'info, TokenAccount>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTerm<'info> {
+    fn create_term(&mut self, bump: u8, maturity_ts: i64) -> Result<()> {
+        let start_rate = self.yi_token.load()?.last_rate;
+        invariant!(start_rate > 0);
+
+        let term = &mut self.term.load_init()?;
+        term.yi_token = self.yi_token.key();
+        term.principal_mint = self.principal_mint.key();
+        term.yield_mint = self.yield_mint.key();
+        term.yi_tokens = self.term_yi_tokens.key();
+        term.maturity_ts = maturity_ts;
+        term.bump = bump;
+
+        term.start_rate = start_rate;
+        term.maturity_rate = 0;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateTerm>, maturity_ts: i64) -> Result<()> {
+    invariant!(maturity_ts > Clock::get()?.unix_timestamp, InvalidMaturity);
+    ctx.accounts
+        .create_term(*unwrap_int!(ctx.bumps.get("term")), maturity_ts)
+}
+
+/// Checks that a principal or yield token [Mint] is new and controlled by the [Term].
+fn assert_term_mint(mint: &Account<Mint>, term: &Pubkey, yi_mint: &Mint) -> Result<()> {
+    assert_keys_eq!(unwrap_opt!(mint.mint_authority), *term);
+    assert_keys_eq!(unwrap_opt!(mint.freeze_authority), *term);
+    invariant!(mint.supply == 0);
+    invariant!(mint.decimals == yi_mint.decimals, DecimalMismatch);
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateTerm<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+
+        assert_keys_neq!(self.principal_mint, self.yield_mint);
+        assert_term_mint(&self.principal_mint, &self.term.key(), &self.yi_mint)?;
+        assert_term_mint(&self.yield_mint, &self.term.key(), &self.yi_mint)?;
+
+        assert_is_zero_token_account!(self.term_yi_tokens);
+        assert_keys_eq!(self.term_yi_tokens.owner, self.term);
+        assert_keys_eq!(self.term_yi_tokens.mint, yi_token.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+//! Processor for [yi::create_voter].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::create_voter].
+#[derive(Accounts)]
+pub struct CreateVoter<'info> {
+    /// [Voter::registrar].
+    pub registrar: AccountLoader<'info, Registrar>,
+    /// [Registrar::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [Voter] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Voter".as_ref(),
+            registrar.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        space = 8 + Voter::SIZE,
+        payer = payer
+    )]
+    pub voter: AccountLoader<'info, Voter>,
+
+    /// The [VoterWeightRecord] of the [Voter] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"VoterWeightRecord".as_ref(),
+            registrar.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        space = 8 + VoterWeightRecord::SIZE,
+        payer = payer
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// [Voter::tokens].
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    /// Owner of the [Voter].
+    pub owner: Signer<'info>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateVoter<'info> {
+    fn create_voter(&mut self, bump: u8) -> Result<()> {
+        let registrar = self.registrar.load()?;
+        let voter = &mut self.voter.load_init()?;
+        voter.registrar = self.registrar.key();
+        voter.owner = self.owner.key();
+        voter.tokens = self.voter_tokens.key();
+        voter.bump = bump;
+
+        let record = &mut self.voter_weight_record;
+        record.realm = registrar.realm;
+        record.governing_token_mint = registrar.governing_token_mint;
+        record.governing_token_owner = self.owner.key();
+        record.voter_weight = 0;
+        record.voter_weight_expiry = Some(0);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<CreateVoter>) -> Result<()> {
+    ctx.accounts
+        .create_voter(*unwrap_int!(ctx.bumps.get("voter")))
+}
+
+impl<'info> Validate<'info> for CreateVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        let registrar = self.registrar.load()?;
+        assert_keys_eq!(registrar.yi_token, self.yi_token);
+        let yi_token = self.yi_token.load()?;
+
+        assert_is_zero_token_account!(self.voter_tokens);
+        assert_keys_eq!(self.voter_tokens.owner, self.voter);
+        assert_keys_eq!(self.voter_tokens.mint, yi_token.mint);
+        Ok(())
+    }
+}
@@ -37,12 +37,43 @@ pub struct CreateYiToken<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Terms of a fixed-maturity [YiToken].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Maturity {
+    /// [YiToken::maturity_ts].
+    pub maturity_ts: i64,
+    /// [YiToken::stake_deadline_ts].
+    pub stake_deadline_ts: i64,
+    /// [YiToken::early_exit_penalty_millibps].
+    pub early_exit_penalty_millibps: u32,
+}
+
+impl Maturity {
+    fn validate(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(self.maturity_ts > now, InvalidMaturity);
+        invariant!(
+            self.stake_deadline_ts == 0 || self.stake_deadline_ts > now,
+            InvalidMaturity
+        );
+        invariant!(
+            self.early_exit_penalty_millibps <= MILLIBPS_PER_WHOLE,
+            InvalidEarlyExitPenalty
+        );
+        Ok(())
+    }
+}
+
 impl<'info> CreateYiToken<'info> {
+    /// Initializes the [YiToken]. All fields must be set here, since the account
+    /// cannot be loaded again until its discriminator is written when the instruction exits.
     fn create_yi_token(
         &mut self,
         bump: u8,
         stake_fee_millibps: u32,
         unstake_fee_millibps: u32,
+        admin: Pubkey,
+        maturity: Maturity,
     ) -> Result<()> {
         let yi_token = &mut self.yi_token.load_init()?;
         yi_token.mint = self.mint.key();
@@ -58,6 +89,15 @@ impl<'info> CreateYiToken<'info> {
 
         yi_token.stake_fee_millibps = stake_fee_millibps;
         yi_token.unstake_fee_millibps = unstake_fee_millibps;
+
+        yi_token.last_rate = RATE_PRECISION;
+        yi_token.rate_last_updated_at = Clock::get()?.unix_timestamp;
+
+        yi_token.admin = admin;
+
+        yi_token.maturity_ts = maturity.maturity_ts;
+        yi_token.stake_deadline_ts = maturity.stake_deadline_ts;
+        yi_token.early_exit_penalty_millibps = maturity.early_exit_penalty_millibps;
         Ok(())
     }
 }
@@ -71,6 +111,57 @@ pub fn handler(
         *unwrap_int!(ctx.bumps.get("yi_token")),
         stake_fee_millibps,
         unstake_fee_millibps,
+        Pubkey::default(),
+        Maturity::default(),
+    )
+}
+
+/// Accounts for [yi::create_yi_token_with_admin].
+#[derive(Accounts)]
+pub struct CreateYiTokenWithAdmin<'info> {
+    /// Create accounts.
+    pub create: CreateYiToken<'info>,
+
+    /// CHECK: Arbitrary account which becomes [YiToken::admin].
+    pub admin: UncheckedAccount<'info>,
+}
+
+pub fn handler_with_admin(
+    ctx: Context<CreateYiTokenWithAdmin>,
+    stake_fee_millibps: u32,
+    unstake_fee_millibps: u32,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.create.create_yi_token(
+        *unwrap_int!(ctx.bumps.get("yi_token")),
+        stake_fee_millibps,
+        unstake_fee_millibps,
+        admin,
+        Maturity::default(),
+    )
+}
+
+pub fn handler_with_maturity(
+    ctx: Context<CreateYiTokenWithAdmin>,
+    stake_fee_millibps: u32,
+    unstake_fee_millibps: u32,
+    maturity_ts: i64,
+    stake_deadline_ts: i64,
+    early_exit_penalty_millibps: u32,
+) -> Result<()> {
+    let maturity = Maturity {
+        maturity_ts,
+        stake_deadline_ts,
+        early_exit_penalty_millibps,
+    };
+    maturity.validate()?;
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.create.create_yi_token(
+        *unwrap_int!(ctx.bumps.get("yi_token")),
+        stake_fee_millibps,
+        unstake_fee_millibps,
+        admin,
+        maturity,
     )
 }
 
@@ -91,3 +182,9 @@ impl<'info> Validate<'info> for CreateYiToken<'info> {
         Ok(())
     }
 }
+
+impl<'info> Validate<'info> for CreateYiTokenWithAdmin<'info> {
+    fn validate(&self) -> Result<()> {
+        self.create.validate()
+    }
+}
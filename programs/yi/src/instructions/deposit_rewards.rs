@@ -0,0 +1,76 @@
+//! Processor for [yi::deposit_rewards].
+
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::deposit_rewards].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct DepositRewards<'info> {
+    /// The [YiToken] to compound rewards into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// Tokens to be deposited into the [YiToken].
+    #[account(mut)]
+    pub source_tokens: Account<'info, TokenAccount>,
+    /// The [TokenAccount::owner] of [Self::source_tokens].
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositRewards<'info> {
+    fn deposit_rewards(&self, amount: u64) -> Result<()> {
+        {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            unwrap_int!(yi_token.record_rewards(amount));
+            let now = Clock::get()?.unix_timestamp;
+            unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+        }
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.yi_underlying_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<DepositRewards>, amount: u64) -> Result<()> {
+    // short circuit if no amount specified
+    if amount == 0 {
+        return Ok(());
+    }
+    ctx.accounts.deposit_rewards(amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for DepositRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        invariant!(self.yi_mint.supply > 0, NoStakedTokens);
+
+        assert_keys_eq!(self.source_tokens.mint, yi_token.underlying_token_mint);
+        assert_keys_eq!(self.source_authority, self.source_tokens.owner);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        Ok(())
+    }
+}
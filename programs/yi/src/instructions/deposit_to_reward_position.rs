@@ -0,0 +1,58 @@
+//! Processor for [yi::deposit_to_reward_position].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::deposit_to_reward_position].
+#[derive(Accounts)]
+pub struct DepositToRewardPosition<'info> {
+    /// The [RewardPosition] receiving the [YiToken]s.
+    pub reward_position: RewardPositionAccounts<'info>,
+
+    /// [RewardPosition::yi_tokens].
+    #[account(mut)]
+    pub position_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// [YiToken]s to deposit, owned by [RewardPosition::owner].
+    #[account(mut)]
+    pub source_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositToRewardPosition<'info> {
+    fn deposit_to_reward_position(&mut self, yitoken_amount: u64) -> Result<()> {
+        self.reward_position.update_rewards()?;
+        self.reward_position.deposit(yitoken_amount)?;
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_yi_tokens.to_account_info(),
+                    to: self.position_yi_tokens.to_account_info(),
+                    authority: self.reward_position.owner.to_account_info(),
+                },
+            ),
+            yitoken_amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<DepositToRewardPosition>, yitoken_amount: u64) -> Result<()> {
+    ctx.accounts.deposit_to_reward_position(yitoken_amount)
+}
+
+impl<'info> Validate<'info> for DepositToRewardPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        self.reward_position.validate()?;
+        assert_keys_eq!(
+            self.reward_position.position.yi_tokens,
+            self.position_yi_tokens
+        );
+        assert_keys_eq!(self.source_yi_tokens.owner, self.reward_position.owner);
+        assert_keys_eq!(self.source_yi_tokens.mint, self.position_yi_tokens.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+//! Processor for [yi::deposit_to_voter].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::deposit_to_voter].
+#[derive(Accounts)]
+pub struct DepositToVoter<'info> {
+    /// The [Voter].
+    pub voter: AccountLoader<'info, Voter>,
+    /// [Voter::tokens].
+    #[account(mut)]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    /// [YiToken]s to be deposited.
+    #[account(mut)]
+    pub source_yi_tokens: Account<'info, TokenAccount>,
+    /// The [TokenAccount::owner] of [Self::source_yi_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositToVoter<'info> {
+    fn deposit_to_voter(&self, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_yi_tokens.to_account_info(),
+                    to: self.voter_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<DepositToVoter>, amount: u64) -> Result<()> {
+    ctx.accounts.deposit_to_voter(amount)
+}
+
+impl<'info> Validate<'info> for DepositToVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        let voter = self.voter.load()?;
+        assert_keys_eq!(self.voter_tokens, voter.tokens);
+        assert_keys_eq!(self.source_yi_tokens.mint, self.voter_tokens.mint);
+        assert_keys_eq!(self.source_authority, self.source_yi_tokens.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Processor for [yi::exit].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::exit].
+#[derive(Accounts)]
+pub struct Exit<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: AccountLoader<'info, Escrow>,
+    /// [Escrow::tokens].
+    #[account(mut)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    /// [Escrow::owner].
+    pub owner: Signer<'info>,
+
+    /// The [TokenAccount] receiving the unlocked [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Exit<'info> {
+    fn exit(&mut self) -> Result<()> {
+        // copied so the escrow is not borrowed during the transfer
+        let escrow = *self.escrow.load()?;
+        invariant!(
+            Clock::get()?.unix_timestamp >= escrow.unlock_at,
+            EscrowLocked
+        );
+        let signer_seeds: &[&[&[u8]]] = escrow_seeds!(escrow);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.escrow_tokens.to_account_info(),
+                    to: self.destination_yi_tokens.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            self.escrow_tokens.amount,
+        )?;
+
+        let escrow = &mut self.escrow.load_mut()?;
+        escrow.amount = 0;
+        escrow.escrow_started_at = 0;
+        escrow.unlock_at = 0;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<Exit>) -> Result<()> {
+    ctx.accounts.exit()
+}
+
+impl<'info> Validate<'info> for Exit<'info> {
+    fn validate(&self) -> Result<()> {
+        let escrow = self.escrow.load()?;
+        assert_keys_eq!(self.escrow_tokens, escrow.tokens);
+        assert_keys_eq!(self.owner, escrow.owner);
+        assert_keys_eq!(self.destination_yi_tokens.mint, self.escrow_tokens.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! Processor for [yi::extend_lock].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::extend_lock].
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: AccountLoader<'info, Escrow>,
+    /// [Escrow::owner].
+    pub owner: Signer<'info>,
+}
+
+impl<'info> ExtendLock<'info> {
+    fn extend_lock(&mut self, unlock_at: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = &mut self.escrow.load_mut()?;
+        check_unlock_at(escrow, unlock_at, now)?;
+        if escrow.unlock_at <= now {
+            escrow.escrow_started_at = now;
+        }
+        escrow.unlock_at = unlock_at;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<ExtendLock>, unlock_at: i64) -> Result<()> {
+    ctx.accounts.extend_lock(unlock_at)
+}
+
+impl<'info> Validate<'info> for ExtendLock<'info> {
+    fn validate(&self) -> Result<()> {
+        let escrow = self.escrow.load()?;
+        assert_keys_eq!(self.owner, escrow.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+//! Accounts shared by instructions which waive fees for a [FeeExemption].
+
+use vipers::prelude::*;
+
+use crate::*;
+
+/// A [FeeExemption].
+#[derive(Accounts)]
+pub struct FeeExemptionAccounts<'info> {
+    /// The [FeeExemption].
+    pub fee_exemption: AccountLoader<'info, FeeExemption>,
+}
+
+impl<'info> FeeExemptionAccounts<'info> {
+    /// Checks that the [FeeExemption] covers `authority`, which has signed.
+    pub(crate) fn assert_exempt(
+        &self,
+        yi_token: &AccountLoader<'info, YiToken>,
+        authority: &Signer<'info>,
+    ) -> Result<()> {
+        let fee_exemption = self.fee_exemption.load()?;
+        assert_keys_eq!(fee_exemption.yi_token, *yi_token);
+        invariant!(fee_exemption.exempt_key == authority.key(), NotFeeExempt);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Processor for [yi::flash_loan].
+
+use anchor_lang::{
+    solana_program::sysvar::{self, instructions as sysvar_instructions},
+    InstructionData,
+};
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::flash_loan].
+#[derive(Accounts)]
+pub struct FlashLoan<'info> {
+    /// The [YiToken] to borrow from.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+    /// The [TokenAccount] receiving the borrowed tokens.
+    #[account(mut)]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The instructions sysvar.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FlashLoan<'info> {
+    /// Ensures that [yi::flash_loan] is invoked directly by the transaction and that
+    /// a [yi::flash_repay] for the same [YiToken] follows it.
+    fn assert_repaid_later(&self) -> Result<()> {
+        let instructions = self.instructions.to_account_info();
+        let current_index: usize =
+            sysvar_instructions::load_current_index_checked(&instructions)?.into();
+
+        let current_ix =
+            sysvar_instructions::load_instruction_at_checked(current_index, &instructions)?;
+        invariant!(current_ix.program_id == crate::ID, FlashLoanCPINotAllowed);
+
+        let repay_data = crate::instruction::FlashRepay {}.data();
+        let mut index = unwrap_int!(current_index.checked_add(1));
+        while let Ok(ix) = sysvar_instructions::load_instruction_at_checked(index, &instructions) {
+            if ix.program_id == crate::ID
+                && ix.data.get(..8) == repay_data.get(..8)
+                && ix
+                    .accounts
+                    .first()
+                    .map(|meta| meta.pubkey == self.yi_token.key())
+                    .unwrap_or(false)
+            {
+                return Ok(());
+            }
+            index = unwrap_int!(index.checked_add(1));
+        }
+        err!(FlashLoanNotRepaid)
+    }
+
+    fn flash_loan(&self, amount: u64) -> Result<()> {
+        self.assert_repaid_later()?;
+        {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            invariant!(yi_token.is_paused == 0, PoolPaused);
+            invariant!(yi_token.flash_loan_amount == 0, FlashLoanInProgress);
+            yi_token.flash_loan_amount = amount;
+        }
+
+        let yi_token = self.yi_token.load()?;
+        let signer_seeds: &[&[&[u8]]] = yitoken_seeds!(yi_token);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.yi_underlying_tokens.to_account_info(),
+                    to: self.destination_tokens.to_account_info(),
+                    authority: self.yi_token.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<FlashLoan>, amount: u64) -> Result<()> {
+    invariant!(amount > 0, ZeroAmount);
+    ctx.accounts.flash_loan(amount)
+}
+
+impl<'info> Validate<'info> for FlashLoan<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        assert_keys_eq!(self.destination_tokens.mint, yi_token.underlying_token_mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+//! Processor for [yi::flash_repay].
+
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::flash_repay].
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    /// The [YiToken] that was borrowed from.
+    ///
+    /// This must be the first account, as it is checked by [yi::flash_loan].
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// Tokens to repay the flash loan with.
+    #[account(mut)]
+    pub source_tokens: Account<'info, TokenAccount>,
+    /// The [TokenAccount::owner] of [Self::source_tokens].
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FlashRepay<'info> {
+    fn flash_repay(&self) -> Result<()> {
+        let repay_amount = {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            let loan_amount = yi_token.flash_loan_amount;
+            invariant!(loan_amount > 0, NoFlashLoan);
+
+            let fee = unwrap_int!(YiToken::calculate_flash_loan_fee(loan_amount));
+            yi_token.flash_loan_amount = 0;
+            unwrap_int!(yi_token.record_rewards(fee));
+            let now = Clock::get()?.unix_timestamp;
+            unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+            unwrap_int!(loan_amount.checked_add(fee))
+        };
+
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_tokens.to_account_info(),
+                    to: self.yi_underlying_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            repay_amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<FlashRepay>) -> Result<()> {
+    ctx.accounts.flash_repay()
+}
+
+impl<'info> Validate<'info> for FlashRepay<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.source_tokens.mint, yi_token.underlying_token_mint);
+        assert_keys_eq!(self.source_authority, self.source_tokens.owner);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Processor for [yi::harvest].
+
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke_signed},
+};
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::harvest].
+///
+/// The remaining accounts are passed through to the adapter program.
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    /// The [YiToken] to compound the yield into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [HarvestAdapter].
+    #[account(mut)]
+    pub adapter: AccountLoader<'info, HarvestAdapter>,
+
+    /// CHECK: [HarvestAdapter::program].
+    pub adapter_program: UncheckedAccount<'info>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+}
+
+/// Emitted when yield is harvested into a [YiToken].
+#[event]
+pub struct HarvestEvent {
+    /// The [YiToken].
+    pub yi_token: Pubkey,
+    /// [HarvestAdapter::program].
+    pub adapter_program: Pubkey,
+    /// Underlying tokens harvested.
+    pub amount: u64,
+    /// When the harvest occurred.
+    pub timestamp: i64,
+}
+
+/// Invokes the global Anchor instruction `name` of an external adapter program with `args`,
+/// passing `tokens` as the first account followed by the `remaining_accounts`.
+///
+/// The [YiToken] never signs for adapters, so an adapter can only add to the accounts passed.
+pub(crate) fn invoke_adapter<'info>(
+    program: &AccountInfo<'info>,
+    name: &str,
+    args: &[u8],
+    tokens: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let accounts: Vec<AccountInfo<'info>> = std::iter::once(tokens.clone())
+        .chain(remaining_accounts.iter().cloned())
+        .collect();
+    invoke_adapter_signed(program, name, args, &accounts, None, &[])
+}
+
+/// Invokes the global Anchor instruction `name` of an external adapter program with `args`
+/// and `accounts`, with `signer` signing via `signer_seeds`.
+pub(crate) fn invoke_adapter_signed<'info>(
+    program: &AccountInfo<'info>,
+    name: &str,
+    args: &[u8],
+    accounts: &[AccountInfo<'info>],
+    signer: Option<&Pubkey>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = accounts
+        .iter()
+        .map(|info| {
+            let is_signer = info.is_signer || Some(info.key) == signer;
+            if info.is_writable {
+                AccountMeta::new(*info.key, is_signer)
+            } else {
+                AccountMeta::new_readonly(*info.key, is_signer)
+            }
+        })
+        .collect();
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(program.clone());
+
+    let mut data = hash(format!("global:{}", name).as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(args);
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts: metas,
+        data,
+    };
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+impl<'info> Harvest<'info> {
+    fn harvest(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let balance_before = self.yi_underlying_tokens.amount;
+        invoke_adapter(
+            &self.adapter_program,
+            "harvest",
+            &[],
+            &self.yi_underlying_tokens.to_account_info(),
+            remaining_accounts,
+        )?;
+        self.yi_underlying_tokens.reload()?;
+        let amount = unwrap_int!(self.yi_underlying_tokens.amount.checked_sub(balance_before));
+
+        let (program_id, data) = unwrap_opt!(get_return_data(), HarvestAmountNotReported);
+        invariant!(
+            program_id == self.adapter_program.key() && data.len() == 8,
+            HarvestAmountNotReported
+        );
+        let reported =
+            u64::from_le_bytes(unwrap_opt!(data.try_into().ok(), HarvestAmountNotReported));
+        invariant!(reported == amount, HarvestAmountMismatch);
+
+        let now = Clock::get()?.unix_timestamp;
+        {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            unwrap_int!(yi_token.record_rewards(amount));
+            unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+        }
+        let adapter = &mut self.adapter.load_mut()?;
+        adapter.total_harvested = unwrap_int!(adapter.total_harvested.checked_add(amount));
+        adapter.last_harvest_ts = now;
+
+        emit!(HarvestEvent {
+            yi_token: self.yi_token.key(),
+            adapter_program: adapter.program,
+            amount,
+            timestamp: now,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>) -> Result<()> {
+    ctx.accounts.harvest(ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for Harvest<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        invariant!(self.yi_mint.supply > 0, NoStakedTokens);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+
+        let adapter = self.adapter.load()?;
+        assert_keys_eq!(adapter.yi_token, self.yi_token);
+        assert_keys_eq!(adapter.program, self.adapter_program);
+        Ok(())
+    }
+}
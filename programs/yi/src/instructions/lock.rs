@@ -0,0 +1,81 @@
+//! Processor for [yi::lock].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::lock].
+#[derive(Accounts)]
+pub struct Lock<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: AccountLoader<'info, Escrow>,
+    /// [Escrow::tokens].
+    #[account(mut)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    /// [Escrow::owner].
+    pub owner: Signer<'info>,
+
+    /// [YiToken]s to be locked.
+    #[account(mut)]
+    pub source_yi_tokens: Account<'info, TokenAccount>,
+    /// The [TokenAccount::owner] of [Self::source_yi_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Checks that `unlock_at` does not shorten the current lock and is within [MAX_ESCROW_LOCK_SECONDS] of `now`.
+pub(crate) fn check_unlock_at(escrow: &Escrow, unlock_at: i64, now: i64) -> Result<()> {
+    invariant!(
+        unlock_at > now && unlock_at >= escrow.unlock_at,
+        EscrowLockTooShort
+    );
+    invariant!(
+        unlock_at <= unwrap_int!(now.checked_add(MAX_ESCROW_LOCK_SECONDS)),
+        EscrowLockTooLong
+    );
+    Ok(())
+}
+
+impl<'info> Lock<'info> {
+    fn lock(&mut self, amount: u64, unlock_at: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        {
+            let escrow = &mut self.escrow.load_mut()?;
+            check_unlock_at(escrow, unlock_at, now)?;
+            escrow.amount = unwrap_int!(escrow.amount.checked_add(amount));
+            if escrow.unlock_at <= now {
+                escrow.escrow_started_at = now;
+            }
+            escrow.unlock_at = unlock_at;
+        }
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_yi_tokens.to_account_info(),
+                    to: self.escrow_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<Lock>, amount: u64, unlock_at: i64) -> Result<()> {
+    ctx.accounts.lock(amount, unlock_at)
+}
+
+impl<'info> Validate<'info> for Lock<'info> {
+    fn validate(&self) -> Result<()> {
+        let escrow = self.escrow.load()?;
+        assert_keys_eq!(self.escrow_tokens, escrow.tokens);
+        assert_keys_eq!(self.owner, escrow.owner);
+        assert_keys_eq!(self.source_yi_tokens.mint, self.escrow_tokens.mint);
+        assert_keys_eq!(self.source_authority, self.source_yi_tokens.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Processor for [yi::merge].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::merge].
+#[derive(Accounts)]
+pub struct Merge<'info> {
+    /// The [Term].
+    pub term: TermAccounts<'info>,
+
+    /// [Term::principal_mint].
+    #[account(mut)]
+    pub principal_mint: Box<Account<'info, Mint>>,
+    /// [Term::yield_mint].
+    #[account(mut)]
+    pub yield_mint: Box<Account<'info, Mint>>,
+
+    /// Principal tokens to be burned.
+    #[account(mut)]
+    pub source_principal_tokens: Box<Account<'info, TokenAccount>>,
+    /// Yield tokens to be burned.
+    #[account(mut)]
+    pub source_yield_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of the source accounts.
+    pub source_authority: Signer<'info>,
+
+    /// The [TokenAccount] receiving the [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> Merge<'info> {
+    fn merge(&self, amount: u64) -> Result<()> {
+        let yitoken_amount = unwrap_int!(self
+            .term
+            .term
+            .load()?
+            .calculate_yitokens_for_term_tokens(amount));
+        self.term.burn(
+            &self.principal_mint,
+            &self.source_principal_tokens,
+            &self.source_authority,
+            amount,
+        )?;
+        self.term.burn(
+            &self.yield_mint,
+            &self.source_yield_tokens,
+            &self.source_authority,
+            amount,
+        )?;
+        self.term
+            .transfer_yi_tokens_out(&self.destination_yi_tokens, yitoken_amount)
+    }
+}
+
+pub fn handler(ctx: Context<Merge>, amount: u64) -> Result<()> {
+    ctx.accounts.merge(amount)
+}
+
+impl<'info> Validate<'info> for Merge<'info> {
+    fn validate(&self) -> Result<()> {
+        self.term.validate()?;
+        let term = self.term.term.load()?;
+        assert_keys_eq!(self.principal_mint, term.principal_mint);
+        assert_keys_eq!(self.yield_mint, term.yield_mint);
+
+        assert_keys_eq!(self.source_principal_tokens.mint, term.principal_mint);
+        assert_keys_eq!(self.source_authority, self.source_principal_tokens.owner);
+        assert_keys_eq!(self.source_yield_tokens.mint, term.yield_mint);
+        assert_keys_eq!(self.source_authority, self.source_yield_tokens.owner);
+        assert_keys_eq!(
+            self.destination_yi_tokens.mint,
+            self.term.term_yi_tokens.mint
+        );
+        Ok(())
+    }
+}
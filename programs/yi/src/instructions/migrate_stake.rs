@@ -0,0 +1,48 @@
+//! Processor for [yi::migrate_stake].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::migrate_stake].
+#[derive(Accounts)]
+pub struct MigrateStake<'info> {
+    /// Unstake accounts of the [YiToken] being exited. The underlying tokens are
+    /// withdrawn to [Unstake::destination_underlying_tokens].
+    pub unstake: Unstake<'info>,
+
+    /// Stake accounts of the [YiToken] being entered. [Stake::source_tokens] must be
+    /// [Unstake::destination_underlying_tokens].
+    pub stake: Stake<'info>,
+}
+
+impl<'info> MigrateStake<'info> {
+    fn migrate_stake(&mut self, yitoken_amount: u64, min_amount_out: u64) -> Result<()> {
+        let withdraw_amount = self.unstake.unstake(yitoken_amount)?;
+        let mint_amount = self.stake.stake(withdraw_amount)?;
+        invariant!(mint_amount >= min_amount_out, MigrationOutputTooLow);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateStake>, yitoken_amount: u64, min_amount_out: u64) -> Result<()> {
+    let yitoken_amount = ctx.accounts.unstake.resolve_amount(yitoken_amount);
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    ctx.accounts.migrate_stake(yitoken_amount, min_amount_out)
+}
+
+impl<'info> Validate<'info> for MigrateStake<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        self.stake.validate()?;
+        assert_keys_neq!(self.unstake.yi_token, self.stake.yi_token);
+        assert_keys_eq!(
+            self.unstake.destination_underlying_tokens,
+            self.stake.source_tokens
+        );
+        assert_keys_eq!(self.unstake.source_authority, self.stake.source_authority);
+        Ok(())
+    }
+}
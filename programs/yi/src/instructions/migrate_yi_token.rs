@@ -0,0 +1,91 @@
+//! Processor for [yi::migrate_yi_token].
+
+use anchor_lang::solana_program::pubkey::PUBKEY_BYTES;
+use anchor_lang::{system_program, Discriminator};
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::migrate_yi_token].
+#[derive(Accounts)]
+pub struct MigrateYiToken<'info> {
+    /// CHECK: The [YiToken] to migrate, which still has [YiToken::LEGACY_SIZE] bytes of data.
+    /// Checked in [MigrateYiToken::validate].
+    #[account(mut)]
+    pub yi_token: UncheckedAccount<'info>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// [YiToken::underlying_tokens].
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateYiToken<'info> {
+    fn fund_rent(&self) -> Result<()> {
+        let required = Rent::get()?.minimum_balance(8 + YiToken::SIZE);
+        let shortfall = required.saturating_sub(self.yi_token.lamports());
+        if shortfall == 0 {
+            return Ok(());
+        }
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.payer.to_account_info(),
+                    to: self.yi_token.to_account_info(),
+                },
+            ),
+            shortfall,
+        )
+    }
+
+    fn migrate_yi_token(&self) -> Result<()> {
+        self.fund_rent()?;
+        self.yi_token.realloc(8 + YiToken::SIZE, true)?;
+
+        let yi_token_info = self.yi_token.to_account_info();
+        let loader: AccountLoader<YiToken> = AccountLoader::try_from(&yi_token_info)?;
+        let yi_token = &mut loader.load_mut()?;
+        // legacy pools priced yiTokens against the vault balance
+        yi_token.total_underlying = self.yi_underlying_tokens.amount;
+        let now = Clock::get()?.unix_timestamp;
+        yi_token.last_rate = unwrap_int!(yi_token.calculate_exchange_rate(self.yi_mint.supply));
+        yi_token.rate_last_updated_at = now;
+        yi_token.rate_checkpoint = yi_token.last_rate;
+        yi_token.rate_checkpoint_at = now;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<MigrateYiToken>) -> Result<()> {
+    ctx.accounts.migrate_yi_token()
+}
+
+impl<'info> Validate<'info> for MigrateYiToken<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(*self.yi_token.owner, crate::ID);
+        let data = self.yi_token.try_borrow_data()?;
+        invariant!(data.len() == 8 + YiToken::LEGACY_SIZE, AlreadyMigrated);
+        if data[..8] != YiToken::discriminator() {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        // the legacy layout is a prefix of the current one
+        assert_keys_eq!(self.yi_mint, Pubkey::new(&data[8..8 + PUBKEY_BYTES]));
+        let underlying_tokens_offset = 8 + PUBKEY_BYTES + 1 + 7 + PUBKEY_BYTES;
+        assert_keys_eq!(
+            self.yi_underlying_tokens,
+            Pubkey::new(&data[underlying_tokens_offset..underlying_tokens_offset + PUBKEY_BYTES])
+        );
+        Ok(())
+    }
+}
@@ -1,9 +1,149 @@
 //! Instructions for the [crate::yi] program.
 
+pub mod batch_stake;
+pub mod batch_unstake;
+pub mod claim_rewards;
+pub mod compound_from;
+pub mod crank_emissions;
+pub mod create_emission_schedule;
+pub mod create_escrow;
+pub mod create_fee_exemption;
+pub mod create_rate_oracle;
+pub mod create_rate_provider;
+pub mod create_referral;
+pub mod create_registrar;
+pub mod create_reward_position;
+pub mod create_reward_stream;
+pub mod create_stake_receipt;
+pub mod create_term;
+pub mod create_voter;
 pub mod create_yi_token;
+pub mod deposit_rewards;
+pub mod deposit_to_reward_position;
+pub mod deposit_to_voter;
+pub mod exit;
+pub mod extend_lock;
+pub mod fee_exemption;
+pub mod flash_loan;
+pub mod flash_repay;
+pub mod harvest;
+pub mod lock;
+pub mod merge;
+pub mod migrate_stake;
+pub mod migrate_yi_token;
+pub mod rebalance;
+pub mod record_rate_observation;
+pub mod redeem_principal;
+pub mod redeem_yield;
+pub mod refresh_rate;
+pub mod register_harvest_adapter;
+pub mod register_strategy;
+pub mod register_swap_route;
+pub mod remove_fee_exemption;
+pub mod reward_position;
+pub mod set_circuit_breaker;
+pub mod set_emission_schedule;
+pub mod set_outflow_limit;
+pub mod set_referral_fee_share;
+pub mod set_reward_rate;
+pub mod set_strategy_ratio;
+pub mod settle_term;
+pub mod split;
 pub mod stake;
+pub mod stake_all_and_close;
+pub mod stake_fee_exempt;
+pub mod stake_sol;
+pub mod stake_to_ata;
+pub mod stake_to_position;
+pub mod stake_with_receipt;
+pub mod stake_with_referral;
+pub mod stake_with_rewards;
+pub mod strategy;
+pub mod sync;
+pub mod term;
 pub mod unstake;
+pub mod unstake_all_and_close;
+pub mod unstake_fee_exempt;
+pub mod unstake_from_position;
+pub mod unstake_sol;
+pub mod unstake_to_ata;
+pub mod unstake_with_recall;
+pub mod unstake_with_receipt;
+pub mod unstake_with_rewards;
+pub mod update_voter_weight_record;
+pub mod withdraw_from_reward_position;
+pub mod withdraw_from_voter;
 
+pub use batch_stake::*;
+pub use batch_unstake::*;
+pub use claim_rewards::*;
+pub use compound_from::*;
+pub use crank_emissions::*;
+pub use create_emission_schedule::*;
+pub use create_escrow::*;
+pub use create_fee_exemption::*;
+pub use create_rate_oracle::*;
+pub use create_rate_provider::*;
+pub use create_referral::*;
+pub use create_registrar::*;
+pub use create_reward_position::*;
+pub use create_reward_stream::*;
+pub use create_stake_receipt::*;
+pub use create_term::*;
+pub use create_voter::*;
 pub use create_yi_token::*;
+pub use deposit_rewards::*;
+pub use deposit_to_reward_position::*;
+pub use deposit_to_voter::*;
+pub use exit::*;
+pub use extend_lock::*;
+pub use fee_exemption::*;
+pub use flash_loan::*;
+pub use flash_repay::*;
+pub use harvest::*;
+pub use lock::*;
+pub use merge::*;
+pub use migrate_stake::*;
+pub use migrate_yi_token::*;
+pub use rebalance::*;
+pub use record_rate_observation::*;
+pub use redeem_principal::*;
+pub use redeem_yield::*;
+pub use refresh_rate::*;
+pub use register_harvest_adapter::*;
+pub use register_strategy::*;
+pub use register_swap_route::*;
+pub use remove_fee_exemption::*;
+pub use reward_position::*;
+pub use set_circuit_breaker::*;
+pub use set_emission_schedule::*;
+pub use set_outflow_limit::*;
+pub use set_referral_fee_share::*;
+pub use set_reward_rate::*;
+pub use set_strategy_ratio::*;
+pub use settle_term::*;
+pub use split::*;
 pub use stake::*;
+pub use stake_all_and_close::*;
+pub use stake_fee_exempt::*;
+pub use stake_sol::*;
+pub use stake_to_ata::*;
+pub use stake_to_position::*;
+pub use stake_with_receipt::*;
+pub use stake_with_referral::*;
+pub use stake_with_rewards::*;
+pub use strategy::*;
+pub use sync::*;
+pub use term::*;
 pub use unstake::*;
+pub use unstake_all_and_close::*;
+pub use unstake_fee_exempt::*;
+pub use unstake_from_position::*;
+pub use unstake_sol::*;
+pub use unstake_to_ata::*;
+pub use unstake_with_recall::*;
+pub use unstake_with_receipt::*;
+pub use unstake_with_rewards::*;
+pub use update_voter_weight_record::*;
+pub use withdraw_from_reward_position::*;
+pub use withdraw_from_voter::*;
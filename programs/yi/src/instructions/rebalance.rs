@@ -0,0 +1,87 @@
+//! Processor for [yi::rebalance].
+
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use std::cmp::Ordering;
+use vipers::prelude::*;
+
+/// Accounts for [yi::rebalance].
+///
+/// The remaining accounts are passed through to the strategy program.
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    /// The [YiToken] to rebalance.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [Strategy] of the [YiToken].
+    pub strategy: StrategyAccounts<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Rebalance<'info> {
+    fn rebalance(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        self.strategy
+            .mark_to_market(&self.yi_token, &self.yi_mint)?;
+        let (deployed, target) = {
+            let yi_token = self.yi_token.load()?;
+            invariant!(yi_token.flash_loan_amount == 0, FlashLoanInProgress);
+            let strategy = self.strategy.strategy.load()?;
+            (
+                yi_token.strategy_deployed,
+                unwrap_int!(strategy.calculate_target(yi_token.total_underlying)),
+            )
+        };
+
+        match deployed.cmp(&target) {
+            Ordering::Less => {
+                // never deploy tokens the vault does not have
+                let amount =
+                    unwrap_int!(target.checked_sub(deployed)).min(self.yi_underlying_tokens.amount);
+                if amount == 0 {
+                    return Ok(());
+                }
+                self.strategy.deploy(
+                    &self.yi_token,
+                    &self.yi_underlying_tokens,
+                    &self.token_program,
+                    remaining_accounts,
+                    amount,
+                )
+            }
+            Ordering::Greater => self.strategy.recall(
+                &self.yi_token,
+                &mut self.yi_underlying_tokens,
+                remaining_accounts,
+                unwrap_int!(deployed.checked_sub(target)),
+            ),
+            Ordering::Equal => Ok(()),
+        }
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Rebalance<'info>>) -> Result<()> {
+    ctx.accounts.rebalance(ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for Rebalance<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+
+        self.strategy.validate()?;
+        let strategy = self.strategy.strategy.load()?;
+        assert_keys_eq!(strategy.yi_token, self.yi_token);
+        Ok(())
+    }
+}
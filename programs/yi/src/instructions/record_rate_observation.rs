@@ -0,0 +1,48 @@
+//! Processor for [yi::record_rate_observation].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::record_rate_observation].
+#[derive(Accounts)]
+pub struct RecordRateObservation<'info> {
+    /// The [YiToken] being observed.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// The [RateOracle] of the [YiToken].
+    #[account(mut)]
+    pub rate_oracle: AccountLoader<'info, RateOracle>,
+}
+
+impl<'info> RecordRateObservation<'info> {
+    fn record_rate_observation(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        let rate_oracle = &mut self.rate_oracle.load_mut()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        // only one observation may be recorded per second
+        if let Some(latest) = rate_oracle.latest() {
+            if latest.timestamp >= now {
+                return Ok(());
+            }
+        }
+        unwrap_int!(rate_oracle.record(RateObservation {
+            timestamp: now,
+            rate_cumulative: unwrap_int!(yi_token.current_rate_cumulative(now)),
+            ..RateObservation::default()
+        }));
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RecordRateObservation>) -> Result<()> {
+    ctx.accounts.record_rate_observation()
+}
+
+impl<'info> Validate<'info> for RecordRateObservation<'info> {
+    fn validate(&self) -> Result<()> {
+        let rate_oracle = self.rate_oracle.load()?;
+        assert_keys_eq!(rate_oracle.yi_token, self.yi_token);
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Processor for [yi::redeem_principal].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::redeem_principal].
+#[derive(Accounts)]
+pub struct RedeemPrincipal<'info> {
+    /// The [Term].
+    pub term: TermAccounts<'info>,
+    /// [Term::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [Term::principal_mint].
+    #[account(mut)]
+    pub principal_mint: Box<Account<'info, Mint>>,
+    /// Principal tokens to be burned.
+    #[account(mut)]
+    pub source_principal_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_principal_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// The [TokenAccount] receiving the [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> RedeemPrincipal<'info> {
+    fn redeem_principal(&self, amount: u64) -> Result<()> {
+        self.term.settle(&self.yi_token)?;
+        let yitoken_amount = unwrap_int!(self
+            .term
+            .term
+            .load()?
+            .calculate_principal_redemption(amount));
+        self.term.burn(
+            &self.principal_mint,
+            &self.source_principal_tokens,
+            &self.source_authority,
+            amount,
+        )?;
+        self.term
+            .transfer_yi_tokens_out(&self.destination_yi_tokens, yitoken_amount)
+    }
+}
+
+pub fn handler(ctx: Context<RedeemPrincipal>, amount: u64) -> Result<()> {
+    ctx.accounts.redeem_principal(amount)
+}
+
+impl<'info> Validate<'info> for RedeemPrincipal<'info> {
+    fn validate(&self) -> Result<()> {
+        self.term.validate()?;
+        let term = self.term.term.load()?;
+        assert_keys_eq!(self.yi_token, term.yi_token);
+        assert_keys_eq!(self.principal_mint, term.principal_mint);
+
+        assert_keys_eq!(self.source_principal_tokens.mint, term.principal_mint);
+        assert_keys_eq!(self.source_authority, self.source_principal_tokens.owner);
+        assert_keys_eq!(
+            self.destination_yi_tokens.mint,
+            self.term.term_yi_tokens.mint
+        );
+        Ok(())
+    }
+}
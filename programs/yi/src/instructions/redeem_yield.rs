@@ -0,0 +1,63 @@
+//! Processor for [yi::redeem_yield].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::redeem_yield].
+#[derive(Accounts)]
+pub struct RedeemYield<'info> {
+    /// The [Term].
+    pub term: TermAccounts<'info>,
+    /// [Term::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [Term::yield_mint].
+    #[account(mut)]
+    pub yield_mint: Box<Account<'info, Mint>>,
+    /// Yield tokens to be burned.
+    #[account(mut)]
+    pub source_yield_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_yield_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// The [TokenAccount] receiving the [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> RedeemYield<'info> {
+    fn redeem_yield(&self, amount: u64) -> Result<()> {
+        self.term.settle(&self.yi_token)?;
+        let yitoken_amount = unwrap_int!(self.term.term.load()?.calculate_yield_redemption(amount));
+        self.term.burn(
+            &self.yield_mint,
+            &self.source_yield_tokens,
+            &self.source_authority,
+            amount,
+        )?;
+        self.term
+            .transfer_yi_tokens_out(&self.destination_yi_tokens, yitoken_amount)
+    }
+}
+
+pub fn handler(ctx: Context<RedeemYield>, amount: u64) -> Result<()> {
+    ctx.accounts.redeem_yield(amount)
+}
+
+impl<'info> Validate<'info> for RedeemYield<'info> {
+    fn validate(&self) -> Result<()> {
+        self.term.validate()?;
+        let term = self.term.term.load()?;
+        assert_keys_eq!(self.yi_token, term.yi_token);
+        assert_keys_eq!(self.yield_mint, term.yield_mint);
+
+        assert_keys_eq!(self.source_yield_tokens.mint, term.yield_mint);
+        assert_keys_eq!(self.source_authority, self.source_yield_tokens.owner);
+        assert_keys_eq!(
+            self.destination_yi_tokens.mint,
+            self.term.term_yi_tokens.mint
+        );
+        Ok(())
+    }
+}
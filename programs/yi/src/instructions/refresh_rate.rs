@@ -0,0 +1,65 @@
+//! Processor for [yi::refresh_rate].
+
+use crate::*;
+use anchor_spl::token::Mint;
+use vipers::prelude::*;
+
+/// Accounts for [yi::refresh_rate].
+#[derive(Accounts)]
+pub struct RefreshRate<'info> {
+    /// The [YiToken] to provide the rate of.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [RateProvider] of the [YiToken].
+    #[account(mut)]
+    pub rate_provider: AccountLoader<'info, RateProvider>,
+}
+
+impl<'info> RefreshRate<'info> {
+    fn refresh_rate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        let clock = Clock::get()?;
+        self.rate_provider.load_mut()?.refresh(
+            unwrap_int!(yi_token.calculate_exchange_rate(self.yi_mint.supply)),
+            clock.slot,
+            clock.unix_timestamp,
+        );
+        Ok(())
+    }
+}
+
+/// Refreshes the [RateProvider] passed as the first remaining account, if any,
+/// to [YiToken::last_rate].
+pub(crate) fn refresh_remaining_rate_provider(
+    yi_token: &AccountLoader<YiToken>,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let rate_provider_info = match remaining_accounts.first() {
+        Some(info) => info,
+        None => return Ok(()),
+    };
+    let rate_provider_loader = AccountLoader::<RateProvider>::try_from(rate_provider_info)?;
+    let rate_provider = &mut rate_provider_loader.load_mut()?;
+    assert_keys_eq!(rate_provider.yi_token, yi_token.key());
+
+    let clock = Clock::get()?;
+    rate_provider.refresh(yi_token.load()?.last_rate, clock.slot, clock.unix_timestamp);
+    Ok(())
+}
+
+pub fn handler(ctx: Context<RefreshRate>) -> Result<()> {
+    ctx.accounts.refresh_rate()
+}
+
+impl<'info> Validate<'info> for RefreshRate<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        let rate_provider = self.rate_provider.load()?;
+        assert_keys_eq!(rate_provider.yi_token, self.yi_token);
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Processor for [yi::register_harvest_adapter].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::register_harvest_adapter].
+#[derive(Accounts)]
+pub struct RegisterHarvestAdapter<'info> {
+    /// The [YiToken] to harvest into.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// CHECK: The adapter program. Checked to be executable.
+    pub adapter_program: UncheckedAccount<'info>,
+
+    /// The [HarvestAdapter] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"HarvestAdapter".as_ref(),
+            yi_token.key().as_ref(),
+            adapter_program.key().as_ref()
+        ],
+        bump,
+        space = 8 + HarvestAdapter::SIZE,
+        payer = payer
+    )]
+    pub adapter: AccountLoader<'info, HarvestAdapter>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterHarvestAdapter<'info> {
+    fn register_harvest_adapter(&mut self, bump: u8) -> Result<()> {
+        let adapter = &mut self.adapter.load_init()?;
+        adapter.yi_token = self.yi_token.key();
+        adapter.program = self.adapter_program.key();
+        adapter.bump = bump;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RegisterHarvestAdapter>) -> Result<()> {
+    ctx.accounts
+        .register_harvest_adapter(*unwrap_int!(ctx.bumps.get("adapter")))
+}
+
+impl<'info> Validate<'info> for RegisterHarvestAdapter<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        invariant!(self.adapter_program.executable);
+        Ok(())
+    }
+}
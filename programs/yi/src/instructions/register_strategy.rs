@@ -0,0 +1,82 @@
+//! Processor for [yi::register_strategy].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::register_strategy].
+#[derive(Accounts)]
+pub struct RegisterStrategy<'info> {
+    /// The [YiToken] whose tokens are deployed.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// CHECK: The strategy program. Checked to be executable.
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// [Strategy::strategy_tokens]. Must be owned by the PDA of the strategy program
+    /// with seeds [STRATEGY_AUTHORITY_SEED] and the [YiToken], so that only that program
+    /// may move deployed tokens.
+    pub strategy_tokens: Account<'info, TokenAccount>,
+
+    /// The [Strategy] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Strategy".as_ref(),
+            yi_token.key().as_ref()
+        ],
+        bump,
+        space = 8 + Strategy::SIZE,
+        payer = payer
+    )]
+    pub strategy: AccountLoader<'info, Strategy>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterStrategy<'info> {
+    fn register_strategy(&mut self, bump: u8, max_deployed_millibps: u32) -> Result<()> {
+        let strategy = &mut self.strategy.load_init()?;
+        strategy.yi_token = self.yi_token.key();
+        strategy.program = self.strategy_program.key();
+        strategy.strategy_tokens = self.strategy_tokens.key();
+        strategy.bump = bump;
+        strategy.max_deployed_millibps = max_deployed_millibps;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RegisterStrategy>, max_deployed_millibps: u32) -> Result<()> {
+    invariant!(
+        max_deployed_millibps <= MAX_STRATEGY_RATIO_MILLIBPS,
+        InvalidStrategyRatio
+    );
+    ctx.accounts.register_strategy(
+        *unwrap_int!(ctx.bumps.get("strategy")),
+        max_deployed_millibps,
+    )
+}
+
+impl<'info> Validate<'info> for RegisterStrategy<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        invariant!(self.strategy_program.executable);
+        assert_keys_eq!(self.strategy_tokens.mint, yi_token.underlying_token_mint);
+        assert_keys_neq!(self.strategy_tokens, yi_token.underlying_tokens);
+        let (strategy_authority, _) = Pubkey::find_program_address(
+            &[STRATEGY_AUTHORITY_SEED, self.yi_token.key().as_ref()],
+            &self.strategy_program.key(),
+        );
+        assert_keys_eq!(self.strategy_tokens.owner, strategy_authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Processor for [yi::register_swap_route].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::register_swap_route].
+#[derive(Accounts)]
+pub struct RegisterSwapRoute<'info> {
+    /// The [YiToken] compounding the swapped tokens.
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// [SwapRoute::input_mint].
+    pub input_mint: Account<'info, Mint>,
+
+    /// CHECK: The swap program. Checked to be executable.
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// The [SwapRoute] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"SwapRoute".as_ref(),
+            yi_token.key().as_ref(),
+            input_mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + SwapRoute::SIZE,
+        payer = payer
+    )]
+    pub route: AccountLoader<'info, SwapRoute>,
+
+    /// [SwapRoute::side_vault].
+    pub side_vault: Account<'info, TokenAccount>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterSwapRoute<'info> {
+    fn register_swap_route(&mut self, bump: u8) -> Result<()> {
+        let route = &mut self.route.load_init()?;
+        route.yi_token = self.yi_token.key();
+        route.input_mint = self.input_mint.key();
+        route.swap_program = self.swap_program.key();
+        route.side_vault = self.side_vault.key();
+        route.bump = bump;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<RegisterSwapRoute>) -> Result<()> {
+    ctx.accounts
+        .register_swap_route(*unwrap_int!(ctx.bumps.get("route")))
+}
+
+impl<'info> Validate<'info> for RegisterSwapRoute<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        assert_keys_neq!(self.input_mint, yi_token.underlying_token_mint);
+        invariant!(self.swap_program.executable);
+
+        assert_keys_eq!(self.side_vault.owner, self.route);
+        assert_keys_eq!(self.side_vault.mint, self.input_mint);
+        invariant!(self.side_vault.delegate.is_none());
+        invariant!(self.side_vault.close_authority.is_none());
+        Ok(())
+    }
+}
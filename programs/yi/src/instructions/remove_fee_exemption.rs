@@ -0,0 +1,36 @@
+//! Processor for [yi::remove_fee_exemption].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::remove_fee_exemption].
+#[derive(Accounts)]
+pub struct RemoveFeeExemption<'info> {
+    /// The [YiToken].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// The [FeeExemption] to be removed.
+    #[account(mut, close = rent_recipient)]
+    pub fee_exemption: AccountLoader<'info, FeeExemption>,
+
+    /// CHECK: Arbitrary account receiving the rent of the [FeeExemption].
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+pub fn handler(_ctx: Context<RemoveFeeExemption>) -> Result<()> {
+    Ok(())
+}
+
+impl<'info> Validate<'info> for RemoveFeeExemption<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        let fee_exemption = self.fee_exemption.load()?;
+        assert_keys_eq!(fee_exemption.yi_token, self.yi_token);
+        Ok(())
+    }
+}
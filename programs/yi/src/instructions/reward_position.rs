@@ -0,0 +1,72 @@
+//! Accounts shared by instructions which update a [RewardPosition].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// A [RewardPosition] and its [RewardStream].
+#[derive(Accounts)]
+pub struct RewardPositionAccounts<'info> {
+    /// The [RewardStream].
+    #[account(mut)]
+    pub stream: Box<Account<'info, RewardStream>>,
+
+    /// The [RewardPosition].
+    #[account(mut)]
+    pub position: Box<Account<'info, RewardPosition>>,
+
+    /// [RewardPosition::owner].
+    pub owner: Signer<'info>,
+}
+
+impl<'info> RewardPositionAccounts<'info> {
+    /// Accrues the [RewardStream] and credits the rewards earned by the [RewardPosition].
+    pub(crate) fn update_rewards(&mut self) -> Result<()> {
+        unwrap_int!(self.stream.accrue(Clock::get()?.unix_timestamp));
+        unwrap_int!(self.position.update_rewards(&self.stream));
+        Ok(())
+    }
+
+    /// Adds shares to the [RewardPosition]. Rewards must be updated first.
+    pub(crate) fn deposit(&mut self, amount: u64) -> Result<()> {
+        unwrap_int!(self.position.deposit(&mut self.stream, amount));
+        Ok(())
+    }
+
+    /// Removes shares from the [RewardPosition]. Rewards must be updated first.
+    pub(crate) fn withdraw(&mut self, amount: u64) -> Result<()> {
+        unwrap_int!(self.position.withdraw(&mut self.stream, amount));
+        Ok(())
+    }
+
+    /// Transfers [YiToken]s out of [RewardPosition::yi_tokens].
+    pub(crate) fn transfer_yi_tokens_out(
+        &self,
+        position_yi_tokens: &Account<'info, TokenAccount>,
+        destination: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+        amount: u64,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = reward_position_seeds!(self.position);
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token::Transfer {
+                    from: position_yi_tokens.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: self.position.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for RewardPositionAccounts<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.position.stream, self.stream);
+        assert_keys_eq!(self.position.owner, self.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Processor for [yi::set_circuit_breaker].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_circuit_breaker].
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    /// The [YiToken].
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+}
+
+/// Emitted when the circuit breaker pauses a [YiToken].
+#[event]
+pub struct CircuitBreakerEvent {
+    /// The [YiToken].
+    pub yi_token: Pubkey,
+    /// [YiToken::rate_checkpoint] when the breaker tripped.
+    pub rate_checkpoint: u64,
+    /// [YiToken::last_rate] when the breaker tripped.
+    pub last_rate: u64,
+    /// When the breaker tripped.
+    pub timestamp: i64,
+}
+
+/// Pauses the [YiToken] and emits a [CircuitBreakerEvent] if [YiToken::last_rate] has jumped
+/// beyond [YiToken::rate_jump_limit_millibps] within the second `now`.
+///
+/// Called after every update of the rate. The operation which moved the rate still succeeds,
+/// so that the pause persists until the admin unpauses via [yi::set_circuit_breaker].
+pub(crate) fn trip_circuit_breaker(
+    yi_token_key: Pubkey,
+    yi_token: &mut YiToken,
+    now: i64,
+) -> Result<()> {
+    if unwrap_int!(yi_token.trip_circuit_breaker(now)) {
+        msg!(
+            "Rate moved from {} to {} within unix timestamp {}; pausing.",
+            yi_token.rate_checkpoint,
+            yi_token.last_rate,
+            now
+        );
+        emit!(CircuitBreakerEvent {
+            yi_token: yi_token_key,
+            rate_checkpoint: yi_token.rate_checkpoint,
+            last_rate: yi_token.last_rate,
+            timestamp: now,
+        });
+    }
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<SetCircuitBreaker>,
+    rate_jump_limit_millibps: u32,
+    is_paused: bool,
+) -> Result<()> {
+    let yi_token = &mut ctx.accounts.yi_token.load_mut()?;
+    yi_token.rate_jump_limit_millibps = rate_jump_limit_millibps;
+    yi_token.is_paused = is_paused.into();
+    // measure jumps from the current rate
+    yi_token.rate_checkpoint = yi_token.last_rate;
+    yi_token.rate_checkpoint_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetCircuitBreaker<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
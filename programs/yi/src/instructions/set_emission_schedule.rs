@@ -0,0 +1,44 @@
+//! Processor for [yi::set_emission_schedule].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_emission_schedule].
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+    /// Crank accounts. Pending emissions are cranked at the previous rate.
+    pub crank: CrankEmissions<'info>,
+
+    /// [EmissionSchedule::authority].
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetEmissionSchedule<'info> {
+    fn set_emission_schedule(&self, emissions_per_second: u64, end_ts: i64) -> Result<()> {
+        self.crank.crank_emissions()?;
+
+        let schedule = &mut self.crank.schedule.load_mut()?;
+        schedule.emissions_per_second = emissions_per_second;
+        schedule.end_ts = end_ts;
+        Ok(())
+    }
+}
+
+pub fn handler(
+    ctx: Context<SetEmissionSchedule>,
+    emissions_per_second: u64,
+    end_ts: i64,
+) -> Result<()> {
+    ctx.accounts
+        .set_emission_schedule(emissions_per_second, end_ts)?;
+    refresh_remaining_rate_provider(&ctx.accounts.crank.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for SetEmissionSchedule<'info> {
+    fn validate(&self) -> Result<()> {
+        self.crank.validate()?;
+        let schedule = self.crank.schedule.load()?;
+        assert_keys_eq!(self.authority, schedule.authority);
+        Ok(())
+    }
+}
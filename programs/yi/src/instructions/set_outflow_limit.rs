@@ -0,0 +1,38 @@
+//! Processor for [yi::set_outflow_limit].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_outflow_limit].
+#[derive(Accounts)]
+pub struct SetOutflowLimit<'info> {
+    /// The [YiToken].
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetOutflowLimit>,
+    outflow_limit: u64,
+    outflow_window_seconds: i64,
+) -> Result<()> {
+    invariant!(
+        outflow_limit == 0 || outflow_window_seconds > 0,
+        InvalidOutflowWindow
+    );
+    let yi_token = &mut ctx.accounts.yi_token.load_mut()?;
+    yi_token.outflow_limit = outflow_limit;
+    yi_token.outflow_window_seconds = outflow_window_seconds;
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetOutflowLimit<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! Processor for [yi::set_referral_fee_share].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_referral_fee_share].
+#[derive(Accounts)]
+pub struct SetReferralFeeShare<'info> {
+    /// The [YiToken].
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetReferralFeeShare>, referral_fee_share_millibps: u32) -> Result<()> {
+    invariant!(
+        referral_fee_share_millibps <= MILLIBPS_PER_WHOLE,
+        InvalidReferralFeeShare
+    );
+    let yi_token = &mut ctx.accounts.yi_token.load_mut()?;
+    yi_token.referral_fee_share_millibps = referral_fee_share_millibps;
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetReferralFeeShare<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
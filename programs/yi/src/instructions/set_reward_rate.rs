@@ -0,0 +1,36 @@
+//! Processor for [yi::set_reward_rate].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_reward_rate].
+#[derive(Accounts)]
+pub struct SetRewardRate<'info> {
+    /// The [RewardStream].
+    #[account(mut)]
+    pub stream: Account<'info, RewardStream>,
+
+    /// [RewardStream::authority].
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetRewardRate<'info> {
+    fn set_reward_rate(&mut self, rewards_per_second: u64) -> Result<()> {
+        let stream = &mut self.stream;
+        // rewards up until now are distributed at the previous rate
+        unwrap_int!(stream.accrue(Clock::get()?.unix_timestamp));
+        stream.rewards_per_second = rewards_per_second;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SetRewardRate>, rewards_per_second: u64) -> Result<()> {
+    ctx.accounts.set_reward_rate(rewards_per_second)
+}
+
+impl<'info> Validate<'info> for SetRewardRate<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.authority, self.stream.authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+//! Processor for [yi::set_strategy_ratio].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::set_strategy_ratio].
+#[derive(Accounts)]
+pub struct SetStrategyRatio<'info> {
+    /// The [YiToken].
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::admin].
+    pub admin: Signer<'info>,
+
+    /// The [Strategy] of the [YiToken].
+    #[account(mut)]
+    pub strategy: AccountLoader<'info, Strategy>,
+}
+
+pub fn handler(ctx: Context<SetStrategyRatio>, max_deployed_millibps: u32) -> Result<()> {
+    invariant!(
+        max_deployed_millibps <= MAX_STRATEGY_RATIO_MILLIBPS,
+        InvalidStrategyRatio
+    );
+    let strategy = &mut ctx.accounts.strategy.load_mut()?;
+    strategy.max_deployed_millibps = max_deployed_millibps;
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetStrategyRatio<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.admin, yi_token.admin);
+        let strategy = self.strategy.load()?;
+        assert_keys_eq!(strategy.yi_token, self.yi_token);
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+//! Processor for [yi::settle_term].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::settle_term].
+#[derive(Accounts)]
+pub struct SettleTerm<'info> {
+    /// The [Term].
+    pub term: TermAccounts<'info>,
+    /// [Term::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+}
+
+pub fn handler(ctx: Context<SettleTerm>) -> Result<()> {
+    ctx.accounts.term.settle(&ctx.accounts.yi_token)
+}
+
+impl<'info> Validate<'info> for SettleTerm<'info> {
+    fn validate(&self) -> Result<()> {
+        self.term.validate()?;
+        let term = self.term.term.load()?;
+        assert_keys_eq!(self.yi_token, term.yi_token);
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! Processor for [yi::split].
+
+use crate::*;
+use anchor_spl::token::{self, Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::split].
+#[derive(Accounts)]
+pub struct Split<'info> {
+    /// The [Term].
+    pub term: TermAccounts<'info>,
+
+    /// [Term::principal_mint].
+    #[account(mut)]
+    pub principal_mint: Box<Account<'info, Mint>>,
+    /// [Term::yield_mint].
+    #[account(mut)]
+    pub yield_mint: Box<Account<'info, Mint>>,
+
+    /// [YiToken]s to be split.
+    #[account(mut)]
+    pub source_yi_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_yi_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// The [TokenAccount] receiving the principal tokens.
+    #[account(mut)]
+    pub destination_principal_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount] receiving the yield tokens.
+    #[account(mut)]
+    pub destination_yield_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> Split<'info> {
+    fn mint_term_tokens(
+        &self,
+        mint: &Account<'info, Mint>,
+        destination: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let term = *self.term.term.load()?;
+        let signer_seeds: &[&[&[u8]]] = term_seeds!(term);
+        token::mint_to(
+            CpiContext::new(
+                self.term.token_program.to_account_info(),
+                token::MintTo {
+                    mint: mint.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: self.term.term.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+
+    fn split(&self, yitoken_amount: u64) -> Result<()> {
+        let amount = {
+            let term = self.term.term.load()?;
+            invariant!(Clock::get()?.unix_timestamp < term.maturity_ts, TermMatured);
+            unwrap_int!(term.calculate_term_tokens_for_yitokens(yitoken_amount))
+        };
+
+        token::transfer(
+            CpiContext::new(
+                self.term.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.source_yi_tokens.to_account_info(),
+                    to: self.term.term_yi_tokens.to_account_info(),
+                    authority: self.source_authority.to_account_info(),
+                },
+            ),
+            yitoken_amount,
+        )?;
+        self.mint_term_tokens(
+            &self.principal_mint,
+            &self.destination_principal_tokens,
+            amount,
+        )?;
+        self.mint_term_tokens(&self.yield_mint, &self.destination_yield_tokens, amount)
+    }
+}
+
+pub fn handler(ctx: Context<Split>, yitoken_amount: u64) -> Result<()> {
+    ctx.accounts.split(yitoken_amount)
+}
+
+impl<'info> Validate<'info> for Split<'info> {
+    fn validate(&self) -> Result<()> {
+        self.term.validate()?;
+        let term = self.term.term.load()?;
+        assert_keys_eq!(self.principal_mint, term.principal_mint);
+        assert_keys_eq!(self.yield_mint, term.yield_mint);
+
+        assert_keys_eq!(self.source_yi_tokens.mint, self.term.term_yi_tokens.mint);
+        assert_keys_eq!(self.source_authority, self.source_yi_tokens.owner);
+        assert_keys_eq!(self.destination_principal_tokens.mint, term.principal_mint);
+        assert_keys_eq!(self.destination_yield_tokens.mint, term.yield_mint);
+        Ok(())
+    }
+}
@@ -6,9 +6,12 @@ use vipers::prelude::*;
 use crate::*;
 
 /// Accounts for [yi::stake].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
 #[derive(Accounts)]
 pub struct Stake<'info> {
     /// The [YiToken] to stake tokens into.
+    #[account(mut)]
     pub yi_token: AccountLoader<'info, YiToken>,
 
     /// [YiToken::mint]. [Mint] of the [YiToken].
@@ -64,25 +67,106 @@ impl<'info> Stake<'info> {
         )
     }
 
-    pub(crate) fn stake(&self, underlying_amount: u64) -> Result<()> {
-        let yi_token = self.yi_token.load()?;
-        self.deposit_underlying(underlying_amount)?;
-        let mint_amount = unwrap_int!(yi_token.calculate_yitokens_for_underlying(
-            underlying_amount,
-            self.yi_underlying_tokens.amount,
-            self.yi_mint.supply
-        ));
+    /// Resolves [ALL_TOKENS] to the full balance of [Self::source_tokens].
+    pub(crate) fn resolve_amount(&self, underlying_amount: u64) -> u64 {
+        if underlying_amount == ALL_TOKENS {
+            self.source_tokens.amount
+        } else {
+            underlying_amount
+        }
+    }
+
+    /// Stakes underlying tokens, returning the number of [YiToken]s minted.
+    pub(crate) fn stake(&self, underlying_amount: u64) -> Result<u64> {
+        let (mint_amount, _) = self.stake_with_options(underlying_amount, None, false)?;
+        Ok(mint_amount)
+    }
+
+    /// Stakes underlying tokens, paying [YiToken::referral_fee_share_millibps] of the stake fee
+    /// to `referrer_tokens` if provided. No stake fee is charged if `fee_exempt`.
+    ///
+    /// Returns the number of [YiToken]s minted and the rebate paid.
+    pub(crate) fn stake_with_options(
+        &self,
+        underlying_amount: u64,
+        referrer_tokens: Option<&Account<'info, TokenAccount>>,
+        fee_exempt: bool,
+    ) -> Result<(u64, u64)> {
+        let now = Clock::get()?.unix_timestamp;
+        let (mint_amount, rebate) = {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            invariant!(yi_token.is_paused == 0, PoolPaused);
+            invariant!(yi_token.can_stake(now), StakingClosed);
+            let fees = yi_token.with_fees_waived(fee_exempt);
+            let mint_amount = unwrap_int!(fees.calculate_yitokens_for_underlying(
+                underlying_amount,
+                yi_token.total_underlying,
+                self.yi_mint.supply
+            ));
+            // rejects stakes rounded down to nothing, e.g. after a large reward deposit
+            invariant!(mint_amount > 0, NothingMinted);
+            let fee = unwrap_int!(fees.calculate_stake_fee(
+                underlying_amount,
+                mint_amount,
+                yi_token.total_underlying,
+                self.yi_mint.supply
+            ));
+            let rebate = if referrer_tokens.is_some() {
+                unwrap_int!(yi_token.calculate_referral_rebate(fee))
+            } else {
+                0
+            };
+            unwrap_int!(yi_token.record_stake(
+                unwrap_int!(underlying_amount.checked_sub(rebate)),
+                unwrap_int!(fee.checked_sub(rebate))
+            ));
+            unwrap_int!(yi_token.update_rate(
+                unwrap_int!(self.yi_mint.supply.checked_add(mint_amount)),
+                now
+            ));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+            (mint_amount, rebate)
+        };
+        self.deposit_underlying(unwrap_int!(underlying_amount.checked_sub(rebate)))?;
+        if let Some(referrer_tokens) = referrer_tokens {
+            if rebate > 0 {
+                token::transfer(
+                    CpiContext::new(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: self.source_tokens.to_account_info(),
+                            to: referrer_tokens.to_account_info(),
+                            authority: self.source_authority.to_account_info(),
+                        },
+                    ),
+                    rebate,
+                )?;
+            }
+        }
         self.mint_yi_tokens(mint_amount)?;
-        Ok(())
+        Ok((mint_amount, rebate))
     }
 }
 
 pub fn handler(ctx: Context<Stake>, underlying_amount: u64) -> Result<()> {
+    let underlying_amount = ctx.accounts.resolve_amount(underlying_amount);
     // short circuit if no amount specified
     if underlying_amount == 0 {
         return Ok(());
     }
-    ctx.accounts.stake(underlying_amount)
+    ctx.accounts.stake(underlying_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+pub fn handler_with_min_out(
+    ctx: Context<Stake>,
+    underlying_amount: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    let underlying_amount = ctx.accounts.resolve_amount(underlying_amount);
+    let mint_amount = ctx.accounts.stake(underlying_amount)?;
+    invariant!(mint_amount >= min_amount_out, StakeOutputTooLow);
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
 }
 
 impl<'info> Validate<'info> for Stake<'info> {
@@ -0,0 +1,49 @@
+//! Processor for [yi::stake_all_and_close].
+
+use anchor_spl::token;
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_all_and_close].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeAllAndClose<'info> {
+    /// Stake accounts. [Stake::source_tokens] is closed after staking.
+    pub stake: Stake<'info>,
+
+    /// CHECK: Receives the rent of [Stake::source_tokens].
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+impl<'info> StakeAllAndClose<'info> {
+    fn stake_all_and_close(&mut self) -> Result<()> {
+        let underlying_amount = self.stake.resolve_amount(ALL_TOKENS);
+        if underlying_amount > 0 {
+            self.stake.stake(underlying_amount)?;
+        }
+        token::close_account(CpiContext::new(
+            self.stake.token_program.to_account_info(),
+            token::CloseAccount {
+                account: self.stake.source_tokens.to_account_info(),
+                destination: self.rent_recipient.to_account_info(),
+                authority: self.stake.source_authority.to_account_info(),
+            },
+        ))
+    }
+}
+
+pub fn handler(ctx: Context<StakeAllAndClose>) -> Result<()> {
+    ctx.accounts.stake_all_and_close()?;
+    refresh_remaining_rate_provider(&ctx.accounts.stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeAllAndClose<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        assert_keys_neq!(self.rent_recipient, self.stake.source_tokens);
+        Ok(())
+    }
+}
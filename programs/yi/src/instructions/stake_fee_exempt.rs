@@ -0,0 +1,36 @@
+//! Processor for [yi::stake_fee_exempt].
+
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_fee_exempt].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeFeeExempt<'info> {
+    /// Stake accounts.
+    pub stake: Stake<'info>,
+
+    /// The [FeeExemption] of [Stake::source_authority].
+    pub exemption: FeeExemptionAccounts<'info>,
+}
+
+pub fn handler(ctx: Context<StakeFeeExempt>, underlying_amount: u64) -> Result<()> {
+    let stake = &ctx.accounts.stake;
+    let underlying_amount = stake.resolve_amount(underlying_amount);
+    // short circuit if no amount specified
+    if underlying_amount == 0 {
+        return Ok(());
+    }
+    stake.stake_with_options(underlying_amount, None, true)?;
+    refresh_remaining_rate_provider(&stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeFeeExempt<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        self.exemption
+            .assert_exempt(&self.stake.yi_token, &self.stake.source_authority)
+    }
+}
@@ -0,0 +1,159 @@
+//! Processor for [yi::stake_sol].
+
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, spl_token, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_sol].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeSol<'info> {
+    /// The [YiToken] to stake lamports into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Box<Account<'info, Mint>>,
+
+    /// Temporary wrapped SOL account, closed at the end of the instruction.
+    #[account(
+        init,
+        seeds = [
+            b"WrappedSol".as_ref(),
+            source_authority.key().as_ref()
+        ],
+        bump,
+        payer = source_authority,
+        token::mint = native_mint,
+        token::authority = source_authority
+    )]
+    pub wrapped_sol: Box<Account<'info, TokenAccount>>,
+    /// The wrapped SOL [Mint].
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    /// Account staking lamports.
+    #[account(mut)]
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [TokenAccount] receiving the minted [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+    /// The [Rent] sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> StakeSol<'info> {
+    /// The [Stake] accounts, staking from [Self::wrapped_sol].
+    fn stake_accounts(&self) -> Stake<'info> {
+        Stake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_tokens: Account::clone(&self.wrapped_sol),
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_yi_tokens: self.destination_yi_tokens.clone(),
+            token_program: self.token_program.clone(),
+        }
+    }
+
+    /// Resolves [ALL_TOKENS] to the lamports held by [Self::source_authority].
+    ///
+    /// The authority keeps the rent of [Self::wrapped_sol], which is returned when it is closed.
+    fn resolve_amount(&self, lamports: u64) -> u64 {
+        if lamports == ALL_TOKENS {
+            self.source_authority.lamports()
+        } else {
+            lamports
+        }
+    }
+
+    fn wrap(&mut self, lamports: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.source_authority.to_account_info(),
+                    to: self.wrapped_sol.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        invoke(
+            &spl_token::instruction::sync_native(&spl_token::ID, &self.wrapped_sol.key())?,
+            &[
+                self.wrapped_sol.to_account_info(),
+                self.token_program.to_account_info(),
+            ],
+        )?;
+        self.wrapped_sol.reload()?;
+        Ok(())
+    }
+
+    fn stake_sol(&mut self, lamports: u64) -> Result<()> {
+        self.wrap(lamports)?;
+        self.stake_accounts().stake(lamports)?;
+        close_wrapped_sol(
+            &self.token_program,
+            &self.wrapped_sol,
+            &self.source_authority,
+        )
+    }
+}
+
+/// Closes a temporary wrapped SOL account, returning its lamports to its owner.
+pub(crate) fn close_wrapped_sol<'info>(
+    token_program: &Program<'info, Token>,
+    wrapped_sol: &Account<'info, TokenAccount>,
+    owner: &Signer<'info>,
+) -> Result<()> {
+    token::close_account(CpiContext::new(
+        token_program.to_account_info(),
+        token::CloseAccount {
+            account: wrapped_sol.to_account_info(),
+            destination: owner.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))
+}
+
+pub fn handler(ctx: Context<StakeSol>, amount: u64) -> Result<()> {
+    let amount = ctx.accounts.resolve_amount(amount);
+    // short circuit if no amount specified
+    if amount == 0 {
+        return close_wrapped_sol(
+            &ctx.accounts.token_program,
+            &ctx.accounts.wrapped_sol,
+            &ctx.accounts.source_authority,
+        );
+    }
+    ctx.accounts.stake_sol(amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeSol<'info> {
+    fn validate(&self) -> Result<()> {
+        {
+            let yi_token = self.yi_token.load()?;
+            assert_keys_eq!(
+                yi_token.underlying_token_mint,
+                spl_token::native_mint::ID,
+                UnderlyingNotNativeMint
+            );
+        }
+        self.stake_accounts().validate()
+    }
+}
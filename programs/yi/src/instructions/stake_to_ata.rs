@@ -0,0 +1,85 @@
+//! Processor for [yi::stake_to_ata].
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_to_ata].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeToAta<'info> {
+    /// The [YiToken] to stake tokens into.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Box<Account<'info, Mint>>,
+
+    /// Tokens to be staked into the [YiToken].
+    #[account(mut)]
+    pub source_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_tokens].
+    pub source_authority: Signer<'info>,
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The associated token account of [Self::source_authority] receiving the minted [YiToken]s.
+    /// Created if it does not exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = yi_mint,
+        associated_token::authority = source_authority
+    )]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Payer of the [Self::destination_yi_tokens] rent, if it is created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+    /// The [anchor_spl::associated_token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+    /// The [Rent] sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> StakeToAta<'info> {
+    /// The [Stake] accounts.
+    fn stake_accounts(&self) -> Stake<'info> {
+        Stake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_tokens: Account::clone(&self.source_tokens),
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_yi_tokens: self.destination_yi_tokens.clone(),
+            token_program: self.token_program.clone(),
+        }
+    }
+}
+
+pub fn handler(ctx: Context<StakeToAta>, underlying_amount: u64) -> Result<()> {
+    let stake = ctx.accounts.stake_accounts();
+    let underlying_amount = stake.resolve_amount(underlying_amount);
+    // short circuit if no amount specified
+    if underlying_amount == 0 {
+        return Ok(());
+    }
+    stake.stake(underlying_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeToAta<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake_accounts().validate()
+    }
+}
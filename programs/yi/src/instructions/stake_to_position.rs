@@ -0,0 +1,121 @@
+//! Processor for [yi::stake_to_position].
+
+use anchor_spl::token::{self, spl_token, Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_to_position].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeToPosition<'info> {
+    /// Stake accounts. [Stake::destination_yi_tokens] becomes [Position::tokens].
+    pub stake: Stake<'info>,
+
+    /// The [Position] to be created.
+    #[account(
+        init,
+        seeds = [
+            b"Position".as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump,
+        space = 8 + Position::SIZE,
+        payer = payer
+    )]
+    pub position: AccountLoader<'info, Position>,
+    /// [Position::mint].
+    #[account(mut)]
+    pub position_mint: Box<Account<'info, Mint>>,
+    /// The [TokenAccount] receiving the position token.
+    #[account(mut)]
+    pub destination_position_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Payer.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeToPosition<'info> {
+    fn mint_position_token(&self, position: &Position) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = position_seeds!(position);
+        token::mint_to(
+            CpiContext::new(
+                self.stake.token_program.to_account_info(),
+                token::MintTo {
+                    mint: self.position_mint.to_account_info(),
+                    to: self.destination_position_tokens.to_account_info(),
+                    authority: self.position.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            1,
+        )?;
+        // no further position tokens may be minted
+        token::set_authority(
+            CpiContext::new(
+                self.stake.token_program.to_account_info(),
+                token::SetAuthority {
+                    current_authority: self.position.to_account_info(),
+                    account_or_mint: self.position_mint.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            spl_token::instruction::AuthorityType::MintTokens,
+            None,
+        )
+    }
+
+    fn stake_to_position(&self, bump: u8, underlying_amount: u64) -> Result<()> {
+        let shares = self.stake.stake(underlying_amount)?;
+        invariant!(shares > 0);
+
+        let position = Position {
+            yi_token: self.stake.yi_token.key(),
+            mint: self.position_mint.key(),
+            tokens: self.stake.destination_yi_tokens.key(),
+            bump,
+            shares,
+            cost_basis: underlying_amount,
+            entry_rate: self.stake.yi_token.load()?.last_rate,
+            entry_ts: Clock::get()?.unix_timestamp,
+            ..Position::default()
+        };
+        *self.position.load_init()? = position;
+
+        self.mint_position_token(&position)
+    }
+}
+
+pub fn handler(ctx: Context<StakeToPosition>, underlying_amount: u64) -> Result<()> {
+    let underlying_amount = ctx.accounts.stake.resolve_amount(underlying_amount);
+    ctx.accounts
+        .stake_to_position(*unwrap_int!(ctx.bumps.get("position")), underlying_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeToPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        assert_is_zero_token_account!(self.stake.destination_yi_tokens);
+        assert_keys_eq!(self.stake.destination_yi_tokens.owner, self.position);
+
+        assert_keys_eq!(
+            unwrap_opt!(self.position_mint.mint_authority),
+            self.position
+        );
+        assert_keys_eq!(
+            unwrap_opt!(self.position_mint.freeze_authority),
+            self.position
+        );
+        invariant!(self.position_mint.supply == 0);
+        invariant!(self.position_mint.decimals == 0);
+
+        assert_keys_eq!(self.destination_position_tokens.mint, self.position_mint);
+        Ok(())
+    }
+}
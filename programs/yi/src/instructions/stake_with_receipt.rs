@@ -0,0 +1,43 @@
+//! Processor for [yi::stake_with_receipt].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::stake_with_receipt].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeWithReceipt<'info> {
+    /// Stake accounts.
+    pub stake: Stake<'info>,
+
+    /// The [StakeReceipt] of [Stake::source_authority].
+    #[account(mut)]
+    pub receipt: AccountLoader<'info, StakeReceipt>,
+}
+
+pub fn handler(ctx: Context<StakeWithReceipt>, underlying_amount: u64) -> Result<()> {
+    let stake = &ctx.accounts.stake;
+    let underlying_amount = stake.resolve_amount(underlying_amount);
+    // short circuit if no amount specified
+    if underlying_amount == 0 {
+        return Ok(());
+    }
+    let mint_amount = stake.stake(underlying_amount)?;
+    unwrap_int!(ctx
+        .accounts
+        .receipt
+        .load_mut()?
+        .record_stake(underlying_amount, mint_amount));
+    refresh_remaining_rate_provider(&stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeWithReceipt<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        let receipt = self.receipt.load()?;
+        assert_keys_eq!(receipt.yi_token, self.stake.yi_token);
+        assert_keys_eq!(receipt.owner, self.stake.source_authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Processor for [yi::stake_with_referral].
+
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::stake_with_referral].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct StakeWithReferral<'info> {
+    /// Stake accounts.
+    pub stake: Stake<'info>,
+
+    /// The [Referral] of the referrer.
+    #[account(mut)]
+    pub referral: AccountLoader<'info, Referral>,
+    /// Underlying token account of [Referral::referrer] receiving the rebate.
+    #[account(mut)]
+    pub referrer_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<StakeWithReferral>, underlying_amount: u64) -> Result<()> {
+    let stake = &ctx.accounts.stake;
+    let underlying_amount = stake.resolve_amount(underlying_amount);
+    // short circuit if no amount specified
+    if underlying_amount == 0 {
+        return Ok(());
+    }
+    let (_, rebate) = stake.stake_with_options(
+        underlying_amount,
+        Some(&ctx.accounts.referrer_tokens),
+        false,
+    )?;
+    unwrap_int!(ctx
+        .accounts
+        .referral
+        .load_mut()?
+        .record_referral(underlying_amount, rebate));
+    refresh_remaining_rate_provider(&stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeWithReferral<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        let referral = self.referral.load()?;
+        assert_keys_eq!(referral.yi_token, self.stake.yi_token);
+        assert_keys_neq!(referral.referrer, self.stake.source_authority);
+
+        assert_keys_eq!(self.referrer_tokens.owner, referral.referrer);
+        assert_keys_eq!(self.referrer_tokens.mint, self.stake.source_tokens.mint);
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+//! Processor for [yi::stake_with_rewards].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::stake_with_rewards].
+#[derive(Accounts)]
+pub struct StakeWithRewards<'info> {
+    /// Stake accounts. The [YiToken]s are minted to [RewardPosition::yi_tokens].
+    pub stake: Stake<'info>,
+
+    /// The [RewardPosition] receiving the [YiToken]s.
+    pub reward_position: RewardPositionAccounts<'info>,
+}
+
+impl<'info> StakeWithRewards<'info> {
+    fn stake_with_rewards(&mut self, underlying_amount: u64) -> Result<()> {
+        self.reward_position.update_rewards()?;
+        let mint_amount = self.stake.stake(underlying_amount)?;
+        self.reward_position.deposit(mint_amount)
+    }
+}
+
+pub fn handler(ctx: Context<StakeWithRewards>, underlying_amount: u64) -> Result<()> {
+    // short circuit if no amount specified
+    if underlying_amount == 0 {
+        return Ok(());
+    }
+    ctx.accounts.stake_with_rewards(underlying_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.stake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for StakeWithRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        self.stake.validate()?;
+        self.reward_position.validate()?;
+        assert_keys_eq!(self.reward_position.stream.yi_token, self.stake.yi_token);
+        assert_keys_eq!(
+            self.reward_position.position.yi_tokens,
+            self.stake.destination_yi_tokens
+        );
+        assert_keys_eq!(self.reward_position.owner, self.stake.source_authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+//! Accounts shared by instructions which move tokens to and from a [Strategy].
+
+use crate::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// A [Strategy] and its program.
+#[derive(Accounts)]
+pub struct StrategyAccounts<'info> {
+    /// The [Strategy].
+    #[account(mut)]
+    pub strategy: AccountLoader<'info, Strategy>,
+
+    /// CHECK: [Strategy::program].
+    pub strategy_program: UncheckedAccount<'info>,
+
+    /// [Strategy::strategy_tokens].
+    #[account(mut)]
+    pub strategy_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> StrategyAccounts<'info> {
+    /// Marks the [Strategy] to the balance of [Strategy::strategy_tokens], realizing any loss
+    /// in the exchange rate before tokens are moved.
+    pub(crate) fn mark_to_market(
+        &mut self,
+        yi_token: &AccountLoader<'info, YiToken>,
+        yi_mint: &Account<'info, Mint>,
+    ) -> Result<()> {
+        self.strategy_tokens.reload()?;
+        let now = Clock::get()?.unix_timestamp;
+        let yi_token_data = &mut yi_token.load_mut()?;
+        let loss = unwrap_int!(yi_token_data.realize_strategy_loss(self.strategy_tokens.amount));
+        if loss == 0 {
+            return Ok(());
+        }
+        let strategy = &mut self.strategy.load_mut()?;
+        strategy.total_losses = unwrap_int!(strategy.total_losses.checked_add(loss));
+        unwrap_int!(yi_token_data.update_rate(yi_mint.supply, now));
+        trip_circuit_breaker(yi_token.key(), yi_token_data, now)
+    }
+
+    /// Moves `amount` underlying tokens from the vault into the [Strategy].
+    pub(crate) fn deploy(
+        &self,
+        yi_token: &AccountLoader<'info, YiToken>,
+        yi_underlying_tokens: &Account<'info, TokenAccount>,
+        token_program: &Program<'info, Token>,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+    ) -> Result<()> {
+        {
+            let strategy = &mut self.strategy.load_mut()?;
+            strategy.total_deployed = unwrap_int!(strategy.total_deployed.checked_add(amount));
+        }
+        {
+            let yi_token = &mut yi_token.load_mut()?;
+            yi_token.strategy_deployed =
+                unwrap_int!(yi_token.strategy_deployed.checked_add(amount));
+        }
+
+        {
+            let yi_token_data = yi_token.load()?;
+            let signer_seeds: &[&[&[u8]]] = yitoken_seeds!(yi_token_data);
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    token::Transfer {
+                        from: yi_underlying_tokens.to_account_info(),
+                        to: self.strategy_tokens.to_account_info(),
+                        authority: yi_token.to_account_info(),
+                    },
+                )
+                .with_signer(signer_seeds),
+                amount,
+            )?;
+        }
+
+        invoke_adapter(
+            &self.strategy_program,
+            "deposit",
+            &amount.to_le_bytes(),
+            &self.strategy_tokens.to_account_info(),
+            remaining_accounts,
+        )
+    }
+
+    /// Recalls `amount` underlying tokens from the [Strategy] into the vault.
+    pub(crate) fn recall(
+        &self,
+        yi_token: &AccountLoader<'info, YiToken>,
+        yi_underlying_tokens: &mut Account<'info, TokenAccount>,
+        remaining_accounts: &[AccountInfo<'info>],
+        amount: u64,
+    ) -> Result<()> {
+        let balance_before = yi_underlying_tokens.amount;
+        invoke_adapter(
+            &self.strategy_program,
+            "withdraw",
+            &amount.to_le_bytes(),
+            &yi_underlying_tokens.to_account_info(),
+            remaining_accounts,
+        )?;
+        yi_underlying_tokens.reload()?;
+        invariant!(
+            yi_underlying_tokens.amount.checked_sub(balance_before) == Some(amount),
+            StrategyRecallMismatch
+        );
+
+        let strategy = &mut self.strategy.load_mut()?;
+        strategy.total_recalled = unwrap_int!(strategy.total_recalled.checked_add(amount));
+        let yi_token = &mut yi_token.load_mut()?;
+        yi_token.strategy_deployed = unwrap_int!(yi_token.strategy_deployed.checked_sub(amount));
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for StrategyAccounts<'info> {
+    fn validate(&self) -> Result<()> {
+        let strategy = self.strategy.load()?;
+        assert_keys_eq!(strategy.program, self.strategy_program);
+        assert_keys_eq!(strategy.strategy_tokens, self.strategy_tokens);
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Processor for [yi::sync].
+
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::sync].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct SyncYiToken<'info> {
+    /// The [YiToken] to sync.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// [YiToken::underlying_tokens].
+    pub yi_underlying_tokens: Account<'info, TokenAccount>,
+
+    /// [YiToken::admin]. Only the admin may recognize direct transfers, since they
+    /// may not be meant as rewards. Rewards should be sent via [yi::deposit_rewards].
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SyncYiToken<'info> {
+    fn sync(&self) -> Result<()> {
+        let yi_token = &mut self.yi_token.load_mut()?;
+        // the vault and strategy can never hold fewer tokens than the pool has accounted for
+        let surplus = unwrap_opt!(
+            unwrap_int!(self
+                .yi_underlying_tokens
+                .amount
+                .checked_add(yi_token.strategy_deployed))
+            .checked_sub(yi_token.total_underlying),
+            UnderlyingTokensMismatch
+        );
+        unwrap_int!(yi_token.record_rewards(surplus));
+        let now = Clock::get()?.unix_timestamp;
+        unwrap_int!(yi_token.update_rate(self.yi_mint.supply, now));
+        trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SyncYiToken>) -> Result<()> {
+    ctx.accounts.sync()?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for SyncYiToken<'info> {
+    fn validate(&self) -> Result<()> {
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+        invariant!(self.yi_mint.supply > 0, NoStakedTokens);
+
+        assert_keys_eq!(self.yi_underlying_tokens, yi_token.underlying_tokens);
+        // pools without an admin can never recognize direct transfers
+        invariant!(yi_token.admin != Pubkey::default(), NoAdmin);
+        assert_keys_eq!(self.admin, yi_token.admin);
+        Ok(())
+    }
+}
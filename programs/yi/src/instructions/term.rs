@@ -0,0 +1,85 @@
+//! Accounts shared by instructions which move [YiToken]s into and out of a [Term].
+
+use crate::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// A [Term] and its [YiToken]s.
+#[derive(Accounts)]
+pub struct TermAccounts<'info> {
+    /// The [Term].
+    #[account(mut)]
+    pub term: AccountLoader<'info, Term>,
+
+    /// [Term::yi_tokens].
+    #[account(mut)]
+    pub term_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TermAccounts<'info> {
+    /// Records [Term::maturity_rate] if the [Term] has not yet been settled.
+    pub(crate) fn settle(&self, yi_token: &AccountLoader<'info, YiToken>) -> Result<()> {
+        let term = &mut self.term.load_mut()?;
+        invariant!(
+            Clock::get()?.unix_timestamp >= term.maturity_ts,
+            TermNotMatured
+        );
+        term.settle(yi_token.load()?.last_rate);
+        Ok(())
+    }
+
+    /// Burns `amount` principal or yield tokens.
+    pub(crate) fn burn(
+        &self,
+        mint: &Account<'info, Mint>,
+        source: &Account<'info, TokenAccount>,
+        authority: &Signer<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        token::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Burn {
+                    mint: mint.to_account_info(),
+                    from: source.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    /// Transfers [YiToken]s out of [Term::yi_tokens].
+    pub(crate) fn transfer_yi_tokens_out(
+        &self,
+        destination: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        // copied so the term is not borrowed during the transfer
+        let term = *self.term.load()?;
+        let signer_seeds: &[&[&[u8]]] = term_seeds!(term);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.term_yi_tokens.to_account_info(),
+                    to: destination.to_account_info(),
+                    authority: self.term.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for TermAccounts<'info> {
+    fn validate(&self) -> Result<()> {
+        let term = self.term.load()?;
+        assert_keys_eq!(self.term_yi_tokens, term.yi_tokens);
+        Ok(())
+    }
+}
@@ -5,9 +5,12 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use vipers::prelude::*;
 
 /// Accounts for [yi::unstake].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
 #[derive(Accounts)]
 pub struct Unstake<'info> {
     /// The [YiToken] to unstake tokens from.
+    #[account(mut)]
     pub yi_token: AccountLoader<'info, YiToken>,
 
     /// [YiToken::mint]. [Mint] of the [YiToken].
@@ -30,6 +33,20 @@ pub struct Unstake<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Records `withdraw_amount` against the outflow limit of the [YiToken], failing with the
+/// time the limit resets if it would be exceeded.
+pub(crate) fn record_outflow(yi_token: &mut YiToken, withdraw_amount: u64, now: i64) -> Result<()> {
+    if !unwrap_int!(yi_token.record_outflow(withdraw_amount, now)) {
+        msg!(
+            "Outflow limit of {} reached; capacity resets at unix timestamp {}.",
+            yi_token.outflow_limit,
+            unwrap_int!(yi_token.outflow_window_end())
+        );
+        return err!(OutflowLimitExceeded);
+    }
+    Ok(())
+}
+
 impl<'info> Unstake<'info> {
     fn withdraw_underlying(&self, amount: u64) -> Result<()> {
         let yi_token = self.yi_token.load()?;
@@ -62,26 +79,78 @@ impl<'info> Unstake<'info> {
         )
     }
 
-    pub(crate) fn unstake(&self, yitoken_amount: u64) -> Result<()> {
-        let yi_token = self.yi_token.load()?;
+    /// Resolves [ALL_TOKENS] to the full balance of [Self::source_yi_tokens].
+    pub(crate) fn resolve_amount(&self, yitoken_amount: u64) -> u64 {
+        if yitoken_amount == ALL_TOKENS {
+            self.source_yi_tokens.amount
+        } else {
+            yitoken_amount
+        }
+    }
+
+    /// Unstakes [YiToken]s, returning the number of underlying tokens withdrawn.
+    pub(crate) fn unstake(&self, yitoken_amount: u64) -> Result<u64> {
+        self.unstake_with_options(yitoken_amount, false)
+    }
+
+    /// Unstakes [YiToken]s, charging no unstake fee if `fee_exempt`.
+    ///
+    /// Returns the number of underlying tokens withdrawn.
+    pub(crate) fn unstake_with_options(
+        &self,
+        yitoken_amount: u64,
+        fee_exempt: bool,
+    ) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let withdraw_amount = {
+            let yi_token = &mut self.yi_token.load_mut()?;
+            invariant!(yi_token.is_paused == 0, PoolPaused);
+            invariant!(yi_token.can_unstake(now), PoolNotMatured);
+            let fees = yi_token.with_fees_waived(fee_exempt);
+            let withdraw_amount = unwrap_int!(fees.calculate_underlying_for_yitokens(
+                yitoken_amount,
+                yi_token.total_underlying,
+                self.yi_mint.supply
+            ));
+            let fee = unwrap_int!(fees.calculate_unstake_fee(
+                yitoken_amount,
+                withdraw_amount,
+                yi_token.total_underlying,
+                self.yi_mint.supply
+            ));
+            // the penalty stays in the pool, compounding to the remaining holders
+            let penalty = unwrap_int!(yi_token.calculate_early_exit_penalty(withdraw_amount, now));
+            let withdraw_amount = unwrap_int!(withdraw_amount.checked_sub(penalty));
+            record_outflow(yi_token, withdraw_amount, now)?;
+            unwrap_int!(
+                yi_token.record_unstake(withdraw_amount, unwrap_int!(fee.checked_add(penalty)))
+            );
+            unwrap_int!(yi_token.update_rate(
+                unwrap_int!(self.yi_mint.supply.checked_sub(yitoken_amount)),
+                now
+            ));
+            trip_circuit_breaker(self.yi_token.key(), yi_token, now)?;
+            withdraw_amount
+        };
+        invariant!(
+            withdraw_amount <= self.yi_underlying_tokens.amount,
+            InsufficientLiquidity
+        );
 
         self.burn_yi_tokens(yitoken_amount)?;
-        let withdraw_amount = unwrap_int!(yi_token.calculate_underlying_for_yitokens(
-            yitoken_amount,
-            self.yi_underlying_tokens.amount,
-            self.yi_mint.supply
-        ));
         self.withdraw_underlying(withdraw_amount)?;
-        Ok(())
+        Ok(withdraw_amount)
     }
 }
 
 pub fn handler(ctx: Context<Unstake>, yitoken_amount: u64) -> Result<()> {
+    let yitoken_amount = ctx.accounts.resolve_amount(yitoken_amount);
     // short circuit if no amount specified
     if yitoken_amount == 0 {
         return Ok(());
     }
-    ctx.accounts.unstake(yitoken_amount)
+    ctx.accounts.unstake(yitoken_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
 }
 
 impl<'info> Validate<'info> for Unstake<'info> {
@@ -0,0 +1,49 @@
+//! Processor for [yi::unstake_all_and_close].
+
+use anchor_spl::token;
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::unstake_all_and_close].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeAllAndClose<'info> {
+    /// Unstake accounts. [Unstake::source_yi_tokens] is closed after unstaking.
+    pub unstake: Unstake<'info>,
+
+    /// CHECK: Receives the rent of [Unstake::source_yi_tokens].
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+impl<'info> UnstakeAllAndClose<'info> {
+    fn unstake_all_and_close(&mut self) -> Result<()> {
+        let yitoken_amount = self.unstake.resolve_amount(ALL_TOKENS);
+        if yitoken_amount > 0 {
+            self.unstake.unstake(yitoken_amount)?;
+        }
+        token::close_account(CpiContext::new(
+            self.unstake.token_program.to_account_info(),
+            token::CloseAccount {
+                account: self.unstake.source_yi_tokens.to_account_info(),
+                destination: self.rent_recipient.to_account_info(),
+                authority: self.unstake.source_authority.to_account_info(),
+            },
+        ))
+    }
+}
+
+pub fn handler(ctx: Context<UnstakeAllAndClose>) -> Result<()> {
+    ctx.accounts.unstake_all_and_close()?;
+    refresh_remaining_rate_provider(&ctx.accounts.unstake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeAllAndClose<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        assert_keys_neq!(self.rent_recipient, self.unstake.source_yi_tokens);
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+//! Processor for [yi::unstake_fee_exempt].
+
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::unstake_fee_exempt].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeFeeExempt<'info> {
+    /// Unstake accounts.
+    pub unstake: Unstake<'info>,
+
+    /// The [FeeExemption] of [Unstake::source_authority].
+    pub exemption: FeeExemptionAccounts<'info>,
+}
+
+pub fn handler(ctx: Context<UnstakeFeeExempt>, yitoken_amount: u64) -> Result<()> {
+    let unstake = &ctx.accounts.unstake;
+    let yitoken_amount = unstake.resolve_amount(yitoken_amount);
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    unstake.unstake_with_options(yitoken_amount, true)?;
+    refresh_remaining_rate_provider(&unstake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeFeeExempt<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        self.exemption
+            .assert_exempt(&self.unstake.yi_token, &self.unstake.source_authority)
+    }
+}
@@ -0,0 +1,108 @@
+//! Processor for [yi::unstake_from_position].
+
+use anchor_spl::token::{self, Mint, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::unstake_from_position].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeFromPosition<'info> {
+    /// Unstake accounts. The redeemed shares pass through [Unstake::source_yi_tokens]
+    /// before being burned.
+    pub unstake: Unstake<'info>,
+
+    /// The [Position].
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+    /// [Position::tokens].
+    #[account(mut)]
+    pub position_yi_tokens: Box<Account<'info, TokenAccount>>,
+    /// [Position::mint].
+    #[account(mut)]
+    pub position_mint: Box<Account<'info, Mint>>,
+    /// The [TokenAccount] of [Unstake::source_authority] holding the position token.
+    #[account(mut)]
+    pub holder_position_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> UnstakeFromPosition<'info> {
+    /// Resolves [ALL_TOKENS] to all remaining [Position::shares].
+    fn resolve_amount(&self, shares: u64) -> Result<u64> {
+        if shares == ALL_TOKENS {
+            Ok(self.position.load()?.shares)
+        } else {
+            Ok(shares)
+        }
+    }
+
+    fn unstake_from_position(&self, shares: u64) -> Result<()> {
+        let position = {
+            let position = &mut self.position.load_mut()?;
+            unwrap_int!(position.record_redemption(shares));
+            **position
+        };
+
+        let signer_seeds: &[&[&[u8]]] = position_seeds!(position);
+        token::transfer(
+            CpiContext::new(
+                self.unstake.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.position_yi_tokens.to_account_info(),
+                    to: self.unstake.source_yi_tokens.to_account_info(),
+                    authority: self.position.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            shares,
+        )?;
+        self.unstake.unstake(shares)?;
+
+        // a fully redeemed position no longer exists
+        if position.shares == 0 {
+            token::burn(
+                CpiContext::new(
+                    self.unstake.token_program.to_account_info(),
+                    token::Burn {
+                        mint: self.position_mint.to_account_info(),
+                        from: self.holder_position_tokens.to_account_info(),
+                        authority: self.unstake.source_authority.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UnstakeFromPosition>, shares: u64) -> Result<()> {
+    let shares = ctx.accounts.resolve_amount(shares)?;
+    // short circuit if no amount specified
+    if shares == 0 {
+        return Ok(());
+    }
+    ctx.accounts.unstake_from_position(shares)?;
+    refresh_remaining_rate_provider(&ctx.accounts.unstake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeFromPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+
+        let position = self.position.load()?;
+        assert_keys_eq!(position.yi_token, self.unstake.yi_token);
+        assert_keys_eq!(self.position_yi_tokens, position.tokens);
+        assert_keys_eq!(self.position_mint, position.mint);
+
+        assert_keys_eq!(self.holder_position_tokens.mint, position.mint);
+        assert_keys_eq!(
+            self.holder_position_tokens.owner,
+            self.unstake.source_authority
+        );
+        invariant!(self.holder_position_tokens.amount == 1);
+        Ok(())
+    }
+}
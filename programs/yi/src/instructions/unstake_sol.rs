@@ -0,0 +1,103 @@
+//! Processor for [yi::unstake_sol].
+
+use anchor_spl::token::{spl_token, Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::unstake_sol].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeSol<'info> {
+    /// The [YiToken] to unstake lamports from.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Box<Account<'info, Mint>>,
+    /// [YiToken]s to be burned.
+    #[account(mut)]
+    pub source_yi_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_yi_tokens]. Receives the unwrapped lamports.
+    #[account(mut)]
+    pub source_authority: Signer<'info>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Temporary wrapped SOL account, closed at the end of the instruction.
+    #[account(
+        init,
+        seeds = [
+            b"WrappedSol".as_ref(),
+            source_authority.key().as_ref()
+        ],
+        bump,
+        payer = source_authority,
+        token::mint = native_mint,
+        token::authority = source_authority
+    )]
+    pub wrapped_sol: Box<Account<'info, TokenAccount>>,
+    /// The wrapped SOL [Mint].
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+    /// The [Rent] sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> UnstakeSol<'info> {
+    /// The [Unstake] accounts, withdrawing into [Self::wrapped_sol].
+    fn unstake_accounts(&self) -> Unstake<'info> {
+        Unstake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_yi_tokens: Account::clone(&self.source_yi_tokens),
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_underlying_tokens: self.wrapped_sol.clone(),
+            token_program: self.token_program.clone(),
+        }
+    }
+
+    fn unstake_sol(&self, yitoken_amount: u64) -> Result<()> {
+        if yitoken_amount > 0 {
+            self.unstake_accounts().unstake(yitoken_amount)?;
+        }
+        close_wrapped_sol(
+            &self.token_program,
+            &self.wrapped_sol,
+            &self.source_authority,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<UnstakeSol>, yitoken_amount: u64) -> Result<()> {
+    let yitoken_amount = ctx
+        .accounts
+        .unstake_accounts()
+        .resolve_amount(yitoken_amount);
+    ctx.accounts.unstake_sol(yitoken_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeSol<'info> {
+    fn validate(&self) -> Result<()> {
+        {
+            let yi_token = self.yi_token.load()?;
+            assert_keys_eq!(
+                yi_token.underlying_token_mint,
+                spl_token::native_mint::ID,
+                UnderlyingNotNativeMint
+            );
+        }
+        self.unstake_accounts().validate()
+    }
+}
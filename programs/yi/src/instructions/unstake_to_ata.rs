@@ -0,0 +1,86 @@
+//! Processor for [yi::unstake_to_ata].
+
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use vipers::prelude::*;
+
+use crate::*;
+
+/// Accounts for [yi::unstake_to_ata].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeToAta<'info> {
+    /// The [YiToken] to unstake tokens from.
+    #[account(mut)]
+    pub yi_token: AccountLoader<'info, YiToken>,
+
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    #[account(mut)]
+    pub yi_mint: Box<Account<'info, Mint>>,
+    /// [YiToken]s to be burned.
+    #[account(mut)]
+    pub source_yi_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount::owner] of [Self::source_yi_tokens].
+    pub source_authority: Signer<'info>,
+
+    /// [YiToken::underlying_tokens].
+    #[account(mut)]
+    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
+    /// [YiToken::underlying_token_mint].
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    /// The associated token account of [Self::source_authority] receiving the underlying tokens.
+    /// Created if it does not exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = source_authority
+    )]
+    pub destination_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Payer of the [Self::destination_underlying_tokens] rent, if it is created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+    /// The [anchor_spl::associated_token] program.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+    /// The [Rent] sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> UnstakeToAta<'info> {
+    /// The [Unstake] accounts.
+    fn unstake_accounts(&self) -> Unstake<'info> {
+        Unstake {
+            yi_token: self.yi_token.clone(),
+            yi_mint: Account::clone(&self.yi_mint),
+            source_yi_tokens: Account::clone(&self.source_yi_tokens),
+            source_authority: self.source_authority.clone(),
+            yi_underlying_tokens: Account::clone(&self.yi_underlying_tokens),
+            destination_underlying_tokens: self.destination_underlying_tokens.clone(),
+            token_program: self.token_program.clone(),
+        }
+    }
+}
+
+pub fn handler(ctx: Context<UnstakeToAta>, yitoken_amount: u64) -> Result<()> {
+    let unstake = ctx.accounts.unstake_accounts();
+    let yitoken_amount = unstake.resolve_amount(yitoken_amount);
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    unstake.unstake(yitoken_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeToAta<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake_accounts().validate()
+    }
+}
@@ -0,0 +1,77 @@
+//! Processor for [yi::unstake_with_recall].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::unstake_with_recall].
+///
+/// The remaining accounts are passed through to the strategy program. If the first remaining
+/// account is the [RateProvider] of the [YiToken], it is refreshed instead of passed through.
+#[derive(Accounts)]
+pub struct UnstakeWithRecall<'info> {
+    /// Unstake accounts.
+    pub unstake: Unstake<'info>,
+
+    /// The [Strategy] of the [YiToken].
+    pub strategy: StrategyAccounts<'info>,
+}
+
+impl<'info> UnstakeWithRecall<'info> {
+    fn unstake_with_recall(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        yitoken_amount: u64,
+    ) -> Result<()> {
+        self.strategy
+            .mark_to_market(&self.unstake.yi_token, &self.unstake.yi_mint)?;
+        let withdraw_amount = {
+            let yi_token = self.unstake.yi_token.load()?;
+            unwrap_int!(yi_token.calculate_underlying_for_yitokens(
+                yitoken_amount,
+                yi_token.total_underlying,
+                self.unstake.yi_mint.supply
+            ))
+        };
+        let liquid = self.unstake.yi_underlying_tokens.amount;
+        if withdraw_amount > liquid {
+            self.strategy.recall(
+                &self.unstake.yi_token,
+                &mut self.unstake.yi_underlying_tokens,
+                remaining_accounts,
+                unwrap_int!(withdraw_amount.checked_sub(liquid)),
+            )?;
+        }
+        self.unstake.unstake(yitoken_amount)?;
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnstakeWithRecall<'info>>,
+    yitoken_amount: u64,
+) -> Result<()> {
+    let yitoken_amount = ctx.accounts.unstake.resolve_amount(yitoken_amount);
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    let num_rate_provider_accounts = match ctx.remaining_accounts.first() {
+        Some(info) if AccountLoader::<RateProvider>::try_from(info).is_ok() => 1,
+        _ => 0,
+    };
+    let (rate_provider_accounts, strategy_accounts) =
+        ctx.remaining_accounts.split_at(num_rate_provider_accounts);
+    ctx.accounts
+        .unstake_with_recall(strategy_accounts, yitoken_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.unstake.yi_token, rate_provider_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeWithRecall<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        self.strategy.validate()?;
+        let strategy = self.strategy.strategy.load()?;
+        assert_keys_eq!(strategy.yi_token, self.unstake.yi_token);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Processor for [yi::unstake_with_receipt].
+
+use crate::*;
+use vipers::prelude::*;
+
+/// Accounts for [yi::unstake_with_receipt].
+///
+/// The [RateProvider] of the [YiToken] may be passed as a remaining account to refresh it.
+#[derive(Accounts)]
+pub struct UnstakeWithReceipt<'info> {
+    /// Unstake accounts.
+    pub unstake: Unstake<'info>,
+
+    /// The [StakeReceipt] of [Unstake::source_authority].
+    #[account(mut)]
+    pub receipt: AccountLoader<'info, StakeReceipt>,
+}
+
+pub fn handler(ctx: Context<UnstakeWithReceipt>, yitoken_amount: u64) -> Result<()> {
+    let unstake = &ctx.accounts.unstake;
+    let yitoken_amount = unstake.resolve_amount(yitoken_amount);
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    invariant!(
+        yitoken_amount <= ctx.accounts.receipt.load()?.yi_tokens,
+        ReceiptBalanceExceeded
+    );
+    let withdraw_amount = unstake.unstake(yitoken_amount)?;
+    unwrap_int!(ctx
+        .accounts
+        .receipt
+        .load_mut()?
+        .record_unstake(yitoken_amount, withdraw_amount));
+    refresh_remaining_rate_provider(&unstake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeWithReceipt<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        let receipt = self.receipt.load()?;
+        assert_keys_eq!(receipt.yi_token, self.unstake.yi_token);
+        assert_keys_eq!(receipt.owner, self.unstake.source_authority);
+        Ok(())
+    }
+}
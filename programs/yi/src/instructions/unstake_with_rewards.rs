@@ -0,0 +1,58 @@
+//! Processor for [yi::unstake_with_rewards].
+
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use vipers::prelude::*;
+
+/// Accounts for [yi::unstake_with_rewards].
+#[derive(Accounts)]
+pub struct UnstakeWithRewards<'info> {
+    /// Unstake accounts. The [YiToken]s are moved from [RewardPosition::yi_tokens]
+    /// to [Unstake::source_yi_tokens] before being burned.
+    pub unstake: Unstake<'info>,
+
+    /// The [RewardPosition] holding the [YiToken]s.
+    pub reward_position: RewardPositionAccounts<'info>,
+
+    /// [RewardPosition::yi_tokens].
+    #[account(mut)]
+    pub position_yi_tokens: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> UnstakeWithRewards<'info> {
+    fn unstake_with_rewards(&mut self, yitoken_amount: u64) -> Result<()> {
+        self.reward_position.update_rewards()?;
+        self.reward_position.withdraw(yitoken_amount)?;
+        self.reward_position.transfer_yi_tokens_out(
+            &self.position_yi_tokens,
+            &self.unstake.source_yi_tokens,
+            &self.unstake.token_program,
+            yitoken_amount,
+        )?;
+        self.unstake.unstake(yitoken_amount)?;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UnstakeWithRewards>, yitoken_amount: u64) -> Result<()> {
+    // short circuit if no amount specified
+    if yitoken_amount == 0 {
+        return Ok(());
+    }
+    ctx.accounts.unstake_with_rewards(yitoken_amount)?;
+    refresh_remaining_rate_provider(&ctx.accounts.unstake.yi_token, ctx.remaining_accounts)
+}
+
+impl<'info> Validate<'info> for UnstakeWithRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        self.unstake.validate()?;
+        self.reward_position.validate()?;
+        assert_keys_eq!(self.reward_position.stream.yi_token, self.unstake.yi_token);
+        assert_keys_eq!(
+            self.reward_position.position.yi_tokens,
+            self.position_yi_tokens
+        );
+        assert_keys_eq!(self.reward_position.owner, self.unstake.source_authority);
+        Ok(())
+    }
+}
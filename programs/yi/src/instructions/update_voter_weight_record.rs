@@ -0,0 +1,71 @@
+//! Processor for [yi::update_voter_weight_record].
+
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::update_voter_weight_record].
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+    /// [Voter::registrar].
+    pub registrar: AccountLoader<'info, Registrar>,
+    /// [Registrar::yi_token].
+    pub yi_token: AccountLoader<'info, YiToken>,
+    /// [YiToken::mint]. [Mint] of the [YiToken].
+    pub yi_mint: Account<'info, Mint>,
+
+    /// The [Voter].
+    pub voter: AccountLoader<'info, Voter>,
+    /// [Voter::tokens].
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    /// The [VoterWeightRecord] of the [Voter].
+    #[account(
+        mut,
+        seeds = [
+            b"VoterWeightRecord".as_ref(),
+            registrar.key().as_ref(),
+            voter_weight_record.governing_token_owner.as_ref()
+        ],
+        bump
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+}
+
+impl<'info> UpdateVoterWeightRecord<'info> {
+    fn update_voter_weight_record(&mut self) -> Result<()> {
+        let voter_weight = {
+            let yi_token = self.yi_token.load()?;
+            unwrap_int!(yi_token.calculate_underlying_for_yitokens(
+                self.voter_tokens.amount,
+                yi_token.total_underlying,
+                self.yi_mint.supply
+            ))
+        };
+        let record = &mut self.voter_weight_record;
+        record.voter_weight = voter_weight;
+        record.voter_weight_expiry = Some(Clock::get()?.slot);
+        record.weight_action = None;
+        record.weight_action_target = None;
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+    ctx.accounts.update_voter_weight_record()
+}
+
+impl<'info> Validate<'info> for UpdateVoterWeightRecord<'info> {
+    fn validate(&self) -> Result<()> {
+        let registrar = self.registrar.load()?;
+        assert_keys_eq!(registrar.yi_token, self.yi_token);
+        let yi_token = self.yi_token.load()?;
+        assert_keys_eq!(self.yi_mint, yi_token.mint);
+
+        let voter = self.voter.load()?;
+        assert_keys_eq!(voter.registrar, self.registrar);
+        assert_keys_eq!(self.voter_tokens, voter.tokens);
+        assert_keys_eq!(self.voter_weight_record.governing_token_owner, voter.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Processor for [yi::withdraw_from_reward_position].
+
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+use vipers::prelude::*;
+
+/// Accounts for [yi::withdraw_from_reward_position].
+#[derive(Accounts)]
+pub struct WithdrawFromRewardPosition<'info> {
+    /// The [RewardPosition] holding the [YiToken]s.
+    pub reward_position: RewardPositionAccounts<'info>,
+
+    /// [RewardPosition::yi_tokens].
+    #[account(mut)]
+    pub position_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [TokenAccount] receiving the [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawFromRewardPosition<'info> {
+    fn withdraw_from_reward_position(&mut self, yitoken_amount: u64) -> Result<()> {
+        self.reward_position.update_rewards()?;
+        self.reward_position.withdraw(yitoken_amount)?;
+        self.reward_position.transfer_yi_tokens_out(
+            &self.position_yi_tokens,
+            &self.destination_yi_tokens,
+            &self.token_program,
+            yitoken_amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawFromRewardPosition>, yitoken_amount: u64) -> Result<()> {
+    ctx.accounts.withdraw_from_reward_position(yitoken_amount)
+}
+
+impl<'info> Validate<'info> for WithdrawFromRewardPosition<'info> {
+    fn validate(&self) -> Result<()> {
+        self.reward_position.validate()?;
+        assert_keys_eq!(
+            self.reward_position.position.yi_tokens,
+            self.position_yi_tokens
+        );
+        assert_keys_eq!(
+            self.destination_yi_tokens.mint,
+            self.position_yi_tokens.mint
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Processor for [yi::withdraw_from_voter].
+
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use vipers::prelude::*;
+
+/// [GovernanceAccountType](https://github.com/solana-labs/solana-program-library/blob/master/governance/program/src/state/enums.rs)
+/// discriminants of a token owner record.
+const TOKEN_OWNER_RECORD_ACCOUNT_TYPES: [u8; 2] = [2, 17];
+
+/// The leading fields of an SPL Governance token owner record, up to and including
+/// its count of unrelinquished votes.
+#[derive(AnchorDeserialize)]
+struct TokenOwnerRecordHeader {
+    account_type: u8,
+    realm: Pubkey,
+    governing_token_mint: Pubkey,
+    governing_token_owner: Pubkey,
+    _governing_token_deposit_amount: u64,
+    unrelinquished_votes_count: u32,
+}
+
+/// Accounts for [yi::withdraw_from_voter].
+#[derive(Accounts)]
+pub struct WithdrawFromVoter<'info> {
+    /// [Voter::registrar].
+    pub registrar: AccountLoader<'info, Registrar>,
+    /// The [Voter].
+    pub voter: AccountLoader<'info, Voter>,
+    /// [Voter::tokens].
+    #[account(mut)]
+    pub voter_tokens: Account<'info, TokenAccount>,
+    /// The [VoterWeightRecord] of the [Voter].
+    #[account(mut)]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+    /// [Voter::owner].
+    pub owner: Signer<'info>,
+    /// CHECK: The SPL Governance token owner record of [Voter::owner]. Checked to have no
+    /// unrelinquished votes, if it exists.
+    pub token_owner_record: UncheckedAccount<'info>,
+
+    /// The [TokenAccount] receiving the withdrawn [YiToken]s.
+    #[account(mut)]
+    pub destination_yi_tokens: Account<'info, TokenAccount>,
+
+    /// The [token] program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawFromVoter<'info> {
+    /// Ensures that the owner has relinquished every vote cast with its current weight,
+    /// so that the withdrawn tokens cannot be counted again by another [Voter].
+    fn assert_no_active_votes(&self, registrar: &Registrar) -> Result<()> {
+        // an owner without a token owner record has never voted
+        if self.token_owner_record.data_is_empty() {
+            return Ok(());
+        }
+        assert_keys_eq!(
+            *self.token_owner_record.owner,
+            registrar.governance_program_id
+        );
+        let data = self.token_owner_record.try_borrow_data()?;
+        let record = TokenOwnerRecordHeader::deserialize(&mut data.as_ref())
+            .map_err(|_| error!(ErrorCode::InvalidTokenOwnerRecord))?;
+        invariant!(
+            TOKEN_OWNER_RECORD_ACCOUNT_TYPES.contains(&record.account_type),
+            InvalidTokenOwnerRecord
+        );
+        assert_keys_eq!(record.realm, registrar.realm);
+        assert_keys_eq!(record.governing_token_mint, registrar.governing_token_mint);
+        assert_keys_eq!(record.governing_token_owner, self.owner);
+        invariant!(record.unrelinquished_votes_count == 0, VoterHasActiveVotes);
+        Ok(())
+    }
+
+    fn withdraw_from_voter(&mut self, amount: u64) -> Result<()> {
+        // copied so the voter is not borrowed during the transfer
+        let voter = *self.voter.load()?;
+        let signer_seeds: &[&[&[u8]]] = voter_seeds!(voter);
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.voter_tokens.to_account_info(),
+                    to: self.destination_yi_tokens.to_account_info(),
+                    authority: self.voter.to_account_info(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )?;
+
+        // the weight must be recomputed before it can be used again
+        let record = &mut self.voter_weight_record;
+        record.voter_weight = 0;
+        record.voter_weight_expiry = Some(Clock::get()?.slot);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawFromVoter>, amount: u64) -> Result<()> {
+    ctx.accounts.withdraw_from_voter(amount)
+}
+
+impl<'info> Validate<'info> for WithdrawFromVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        let registrar = self.registrar.load()?;
+        let voter = self.voter.load()?;
+        assert_keys_eq!(voter.registrar, self.registrar);
+        assert_keys_eq!(self.voter_tokens, voter.tokens);
+        assert_keys_eq!(self.owner, voter.owner);
+        assert_keys_eq!(self.voter_weight_record.governing_token_owner, voter.owner);
+        let (expected_record, _) = Pubkey::find_program_address(
+            &[
+                b"VoterWeightRecord",
+                voter.registrar.as_ref(),
+                voter.owner.as_ref(),
+            ],
+            &crate::ID,
+        );
+        assert_keys_eq!(self.voter_weight_record, expected_record);
+        assert_keys_eq!(self.destination_yi_tokens.mint, self.voter_tokens.mint);
+
+        let (expected_token_owner_record, _) = Pubkey::find_program_address(
+            &[
+                b"governance",
+                registrar.realm.as_ref(),
+                registrar.governing_token_mint.as_ref(),
+                voter.owner.as_ref(),
+            ],
+            &registrar.governance_program_id,
+        );
+        assert_keys_eq!(self.token_owner_record, expected_token_owner_record);
+        self.assert_no_active_votes(&registrar)
+    }
+}
@@ -15,10 +15,41 @@
 //! First, create a Yi Token by invoking the [`yi::create_yi_token`] instruction. Then, anyone may stake
 //! tokens into the pool via [`yi::stake`].
 //!
-//! To send auto-compounded rewards to the pool, deposit tokens to the [`YiToken::underlying_tokens`] token account.
-//! This will increase the conversion rate of Yi Tokens to underlying tokens.
+//! To send auto-compounded rewards to the pool, invoke [`yi::deposit_rewards`].
+//! This will increase the conversion rate of Yi Tokens to underlying tokens. Since anyone may deposit rewards, stakes
+//! which would mint no Yi Tokens are rejected, and [`yi::stake_with_min_out`] bounds the Yi Tokens minted.
 //!
-//! To exit the pool, invoke [`yi::unstake`].
+//! The pool tracks its underlying tokens internally in [`YiToken::total_underlying`], so tokens
+//! transferred directly to the [`YiToken::underlying_tokens`] token account do not change the conversion rate
+//! until the [`YiToken::admin`] recognizes them by invoking [`yi::sync`]. Pools without an admin cannot sync, so tokens
+//! transferred directly to them stay in the vault without compounding. Pools created before internal accounting
+//! must first be resized via [`yi::migrate_yi_token`], which anyone may invoke.
+//!
+//! Integrations which need the exchange rate as plain account data should read [`YiToken::last_rate`], which every
+//! operation that changes the rate keeps current. A [`RateProvider`] holds a copy of the rate in a separate account,
+//! refreshed via [`yi::refresh_rate`] or by operations passed it as a remaining account.
+//!
+//! To compound rewards continuously, fund an [`EmissionSchedule`] created via [`yi::create_emission_schedule`].
+//! Anyone may invoke [`yi::crank_emissions`] to move the tokens emitted so far into the pool, earning an optional bounty.
+//!
+//! Partners may reward yiToken holders in another token via a [`RewardStream`], created by the [`YiToken::admin`] via
+//! [`yi::create_reward_stream`]. Only yiTokens held in a [`RewardPosition`], via [`yi::stake_with_rewards`] or
+//! [`yi::deposit_to_reward_position`], earn from the stream; [`yi::stake`], [`yi::unstake`] and the other instructions do not
+//! settle rewards, so yiTokens must be deposited into the position to earn.
+//!
+//! yiTokens may be locked in an [`Escrow`] via [`yi::lock`] for voting power proportional to the amount and remaining lock time.
+//! Locked yiTokens keep compounding and are withdrawn via [`yi::exit`] once the lock expires.
+//!
+//! DAOs on SPL Governance may create a [`Registrar`] so that yiToken holders vote with the underlying value of the yiTokens
+//! they deposit into a [`Voter`], written to a [`VoterWeightRecord`] via [`yi::update_voter_weight_record`].
+//! Deposits may only be withdrawn via [`yi::withdraw_from_voter`] once the owner has relinquished all of its votes.
+//!
+//! A [`Term`] splits yiTokens into principal tokens, which redeem for a fixed amount of underlying tokens at maturity,
+//! and yield tokens, which redeem for the growth of the exchange rate until maturity.
+//! Anyone may fix the exchange rate at maturity via [`yi::settle_term`]; otherwise it is fixed by the first redemption.
+//!
+//! To exit the pool, invoke [`yi::unstake`]. Passing [`ALL_TOKENS`] as the amount unstakes the full balance, so no dust is left behind;
+//! [`yi::unstake_all_and_close`] additionally closes the emptied yiToken account.
 //!
 //! ## Fees
 //!
@@ -57,6 +88,32 @@ declare_id!("YiiTopEnX2vyoWdXuG45ovDFYZars4XZ4w6td6RVTFm");
 /// Number of millibps in 1.
 pub const MILLIBPS_PER_WHOLE: u32 = 10_000 * 1_000;
 
+/// Exchange rates are expressed as underlying tokens per yiToken, multiplied by this value.
+pub const RATE_PRECISION: u64 = 1_000_000_000_000;
+
+/// Fee charged on flash loans, in thousands of bps. This fee accrues to the [YiToken] holders.
+pub const FLASH_LOAN_FEE_MILLIBPS: u32 = 9 * 1_000;
+
+/// [RewardStream::reward_per_share] is multiplied by this value.
+pub const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Passed as the amount to [yi::stake] or [yi::unstake] to use the full balance of the source account.
+pub const ALL_TOKENS: u64 = u64::MAX;
+
+/// Maximum bounty paid to the caller of [yi::crank_emissions], in thousands of bps.
+pub const MAX_EMISSION_BOUNTY_MILLIBPS: u32 = 100 * 1_000;
+
+/// Maximum share of a [YiToken] which may be deployed to its [Strategy], in thousands of bps.
+/// The rest stays in [YiToken::underlying_tokens] as a liquidity buffer for unstakes.
+pub const MAX_STRATEGY_RATIO_MILLIBPS: u32 = 9_500 * 1_000;
+
+/// Seed of the PDA of a [Strategy::program] which must own [Strategy::strategy_tokens],
+/// followed by the [YiToken] key.
+pub const STRATEGY_AUTHORITY_SEED: &[u8] = b"StrategyAuthority";
+
+/// Maximum duration of an [Escrow] lock. Voting power is scaled against this duration.
+pub const MAX_ESCROW_LOCK_SECONDS: i64 = 4 * 365 * 24 * 60 * 60;
+
 /// The [yi] program.
 #[program]
 pub mod yi {
@@ -79,17 +136,597 @@ pub mod yi {
         create_yi_token::handler(ctx, stake_fee_millibps, unstake_fee_millibps)
     }
 
+    /// Creates a [YiToken] with fees and a [YiToken::admin], which may register
+    /// integrations such as [HarvestAdapter]s. Fees still cannot be modified.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_yi_token_with_admin(
+        ctx: Context<CreateYiTokenWithAdmin>,
+        stake_fee_millibps: u32,
+        unstake_fee_millibps: u32,
+    ) -> Result<()> {
+        create_yi_token::handler_with_admin(ctx, stake_fee_millibps, unstake_fee_millibps)
+    }
+
+    /// Creates a fixed-maturity [YiToken] with a [YiToken::admin].
+    ///
+    /// Unstaking is rejected before `maturity_ts` unless `early_exit_penalty_millibps` is
+    /// non-zero, in which case early exits forfeit that share of their underlying to the
+    /// remaining holders. If `stake_deadline_ts` is non-zero, staking closes at that time.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_yi_token_with_maturity(
+        ctx: Context<CreateYiTokenWithAdmin>,
+        stake_fee_millibps: u32,
+        unstake_fee_millibps: u32,
+        maturity_ts: i64,
+        stake_deadline_ts: i64,
+        early_exit_penalty_millibps: u32,
+    ) -> Result<()> {
+        create_yi_token::handler_with_maturity(
+            ctx,
+            stake_fee_millibps,
+            unstake_fee_millibps,
+            maturity_ts,
+            stake_deadline_ts,
+            early_exit_penalty_millibps,
+        )
+    }
+
+    /// Resizes a [YiToken] created before internal accounting to the current layout, seeding
+    /// [YiToken::total_underlying] from the balance of [YiToken::underlying_tokens].
+    ///
+    /// Permissionless, since it does not change the exchange rate. The payer funds the additional rent.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_yi_token(ctx: Context<MigrateYiToken>) -> Result<()> {
+        migrate_yi_token::handler(ctx)
+    }
+
     /// Stakes underlying tokens for yiTokens.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    /// The minted yiTokens do not earn from [RewardStream]s; see [stake_with_rewards].
+    /// Not recorded in [StakeReceipt]s; see [stake_with_receipt].
     #[access_control(ctx.accounts.validate())]
     pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
         stake::handler(ctx, amount)
     }
 
+    /// Stakes underlying tokens, failing if fewer than `min_amount_out` yiTokens would be minted.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_with_min_out(ctx: Context<Stake>, amount: u64, min_amount_out: u64) -> Result<()> {
+        stake::handler_with_min_out(ctx, amount, min_amount_out)
+    }
+
     /// Unstakes yiTokens for their underlying tokens.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is unstaked.
+    /// Does not settle [RewardStream]s; see [unstake_with_rewards].
+    /// Not recorded in [StakeReceipt]s; see [unstake_with_receipt].
     #[access_control(ctx.accounts.validate())]
     pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
         unstake::handler(ctx, amount)
     }
+
+    /// Stakes underlying tokens from many source accounts owned by the same authority.
+    ///
+    /// Each amount is paired with a (source, destination) pair of remaining accounts.
+    #[access_control(ctx.accounts.validate())]
+    pub fn batch_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchStake<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        batch_stake::handler(ctx, amounts)
+    }
+
+    /// Unstakes yiTokens from many source accounts owned by the same authority.
+    ///
+    /// Each amount is paired with a (source, destination) pair of remaining accounts.
+    #[access_control(ctx.accounts.validate())]
+    pub fn batch_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchUnstake<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        batch_unstake::handler(ctx, amounts)
+    }
+
+    /// Unstakes yiTokens of one [YiToken] and stakes the withdrawn underlying tokens into another
+    /// [YiToken] with the same underlying mint, minting at least `min_amount_out` yiTokens.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is migrated.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_stake(
+        ctx: Context<MigrateStake>,
+        amount: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        migrate_stake::handler(ctx, amount, min_amount_out)
+    }
+
+    /// Stakes the full balance of the source account, then closes it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_all_and_close(ctx: Context<StakeAllAndClose>) -> Result<()> {
+        stake_all_and_close::handler(ctx)
+    }
+
+    /// Unstakes the full balance of the source account, then closes it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_all_and_close(ctx: Context<UnstakeAllAndClose>) -> Result<()> {
+        unstake_all_and_close::handler(ctx)
+    }
+
+    /// Stakes underlying tokens, creating the staker's yiToken associated token account if needed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_to_ata(ctx: Context<StakeToAta>, amount: u64) -> Result<()> {
+        stake_to_ata::handler(ctx, amount)
+    }
+
+    /// Unstakes yiTokens, creating the staker's underlying associated token account if needed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_to_ata(ctx: Context<UnstakeToAta>, amount: u64) -> Result<()> {
+        unstake_to_ata::handler(ctx, amount)
+    }
+
+    /// Stakes underlying tokens into a new [Position], minting a one-of-one position token
+    /// in place of fungible yiTokens.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_to_position(ctx: Context<StakeToPosition>, amount: u64) -> Result<()> {
+        stake_to_position::handler(ctx, amount)
+    }
+
+    /// Redeems `shares` of a [Position] for underlying tokens. The position token is burned
+    /// once all shares have been redeemed.
+    ///
+    /// If `shares` is [ALL_TOKENS], all remaining shares are redeemed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_from_position(ctx: Context<UnstakeFromPosition>, shares: u64) -> Result<()> {
+        unstake_from_position::handler(ctx, shares)
+    }
+
+    /// Creates a [StakeReceipt] recording the owner's stakes and unstakes for gain reporting.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_stake_receipt(ctx: Context<CreateStakeReceipt>) -> Result<()> {
+        create_stake_receipt::handler(ctx)
+    }
+
+    /// Stakes underlying tokens, recording the stake on the staker's [StakeReceipt].
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_with_receipt(ctx: Context<StakeWithReceipt>, amount: u64) -> Result<()> {
+        stake_with_receipt::handler(ctx, amount)
+    }
+
+    /// Unstakes yiTokens, recording the unstake on the staker's [StakeReceipt].
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is unstaked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_with_receipt(ctx: Context<UnstakeWithReceipt>, amount: u64) -> Result<()> {
+        unstake_with_receipt::handler(ctx, amount)
+    }
+
+    /// Creates a [Referral] tracking the stakes brought to a [YiToken] by a referrer.
+    /// Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_referral(ctx: Context<CreateReferral>) -> Result<()> {
+        create_referral::handler(ctx)
+    }
+
+    /// Stakes underlying tokens on behalf of a referrer, who receives
+    /// [YiToken::referral_fee_share_millibps] of the stake fee.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_with_referral(ctx: Context<StakeWithReferral>, amount: u64) -> Result<()> {
+        stake_with_referral::handler(ctx, amount)
+    }
+
+    /// Sets [YiToken::referral_fee_share_millibps]. Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_referral_fee_share(
+        ctx: Context<SetReferralFeeShare>,
+        referral_fee_share_millibps: u32,
+    ) -> Result<()> {
+        set_referral_fee_share::handler(ctx, referral_fee_share_millibps)
+    }
+
+    /// Creates a [FeeExemption] waiving fees for `exempt_key`, an authority which may be a program's PDA.
+    /// Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_fee_exemption(
+        ctx: Context<CreateFeeExemption>,
+        exempt_key: Pubkey,
+    ) -> Result<()> {
+        create_fee_exemption::handler(ctx, exempt_key)
+    }
+
+    /// Removes a [FeeExemption]. Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        remove_fee_exemption::handler(ctx)
+    }
+
+    /// Stakes underlying tokens without a stake fee. The staker must have a [FeeExemption].
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_fee_exempt(ctx: Context<StakeFeeExempt>, amount: u64) -> Result<()> {
+        stake_fee_exempt::handler(ctx, amount)
+    }
+
+    /// Unstakes yiTokens without an unstake fee. The staker must have a [FeeExemption].
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is unstaked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_fee_exempt(ctx: Context<UnstakeFeeExempt>, amount: u64) -> Result<()> {
+        unstake_fee_exempt::handler(ctx, amount)
+    }
+
+    /// Limits unstakes to `outflow_limit` underlying tokens per `outflow_window_seconds`.
+    /// A limit of zero removes the limit. Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_outflow_limit(
+        ctx: Context<SetOutflowLimit>,
+        outflow_limit: u64,
+        outflow_window_seconds: i64,
+    ) -> Result<()> {
+        set_outflow_limit::handler(ctx, outflow_limit, outflow_window_seconds)
+    }
+
+    /// Configures the exchange rate circuit breaker, which pauses the pool once the rate has fallen
+    /// or risen more than `rate_jump_limit_millibps` within a second. A limit of zero disables it.
+    ///
+    /// Also pauses or unpauses the pool. Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        rate_jump_limit_millibps: u32,
+        is_paused: bool,
+    ) -> Result<()> {
+        set_circuit_breaker::handler(ctx, rate_jump_limit_millibps, is_paused)
+    }
+
+    /// Stakes lamports into a wrapped SOL [YiToken], wrapping them in a temporary account.
+    ///
+    /// If `amount` is [ALL_TOKENS], all lamports of the source authority are staked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_sol(ctx: Context<StakeSol>, amount: u64) -> Result<()> {
+        stake_sol::handler(ctx, amount)
+    }
+
+    /// Unstakes yiTokens of a wrapped SOL [YiToken], unwrapping the withdrawn tokens into lamports.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is unstaked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_sol(ctx: Context<UnstakeSol>, amount: u64) -> Result<()> {
+        unstake_sol::handler(ctx, amount)
+    }
+
+    /// Deposits underlying tokens into the pool as rewards for the [YiToken] holders.
+    #[access_control(ctx.accounts.validate())]
+    pub fn deposit_rewards(ctx: Context<DepositRewards>, amount: u64) -> Result<()> {
+        deposit_rewards::handler(ctx, amount)
+    }
+
+    /// Recognizes any underlying tokens sent directly to [YiToken::underlying_tokens]
+    /// as rewards for the [YiToken] holders. Only callable by the [YiToken::admin], so pools
+    /// without one fail with [ErrorCode::NoAdmin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn sync(ctx: Context<SyncYiToken>) -> Result<()> {
+        sync::handler(ctx)
+    }
+
+    /// Creates a [RateOracle] for a [YiToken].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_rate_oracle(ctx: Context<CreateRateOracle>) -> Result<()> {
+        create_rate_oracle::handler(ctx)
+    }
+
+    /// Records the current [YiToken::rate_cumulative] into the [RateOracle].
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn record_rate_observation(ctx: Context<RecordRateObservation>) -> Result<()> {
+        record_rate_observation::handler(ctx)
+    }
+
+    /// Creates a [RateProvider] for a [YiToken].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_rate_provider(ctx: Context<CreateRateProvider>) -> Result<()> {
+        create_rate_provider::handler(ctx)
+    }
+
+    /// Refreshes the rate stored in a [RateProvider].
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn refresh_rate(ctx: Context<RefreshRate>) -> Result<()> {
+        refresh_rate::handler(ctx)
+    }
+
+    /// Lends underlying tokens from the pool within a transaction.
+    /// The transaction must invoke [yi::flash_repay] after this instruction.
+    /// Rejected while the pool is paused.
+    #[access_control(ctx.accounts.validate())]
+    pub fn flash_loan(ctx: Context<FlashLoan>, amount: u64) -> Result<()> {
+        flash_loan::handler(ctx, amount)
+    }
+
+    /// Repays an outstanding flash loan plus the [FLASH_LOAN_FEE_MILLIBPS] fee,
+    /// which is compounded into the pool.
+    #[access_control(ctx.accounts.validate())]
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+        flash_repay::handler(ctx)
+    }
+
+    /// Creates a [RewardStream] of a secondary reward token for [YiToken] holders.
+    /// Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_reward_stream(ctx: Context<CreateRewardStream>) -> Result<()> {
+        create_reward_stream::handler(ctx)
+    }
+
+    /// Sets the number of reward tokens a [RewardStream] distributes per second.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_reward_rate(ctx: Context<SetRewardRate>, rewards_per_second: u64) -> Result<()> {
+        set_reward_rate::handler(ctx, rewards_per_second)
+    }
+
+    /// Creates a [RewardPosition] for earning from a [RewardStream].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_reward_position(ctx: Context<CreateRewardPosition>) -> Result<()> {
+        create_reward_position::handler(ctx)
+    }
+
+    /// Stakes underlying tokens, depositing the minted yiTokens into a [RewardPosition].
+    #[access_control(ctx.accounts.validate())]
+    pub fn stake_with_rewards(ctx: Context<StakeWithRewards>, amount: u64) -> Result<()> {
+        stake_with_rewards::handler(ctx, amount)
+    }
+
+    /// Unstakes yiTokens held in a [RewardPosition] for their underlying tokens.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_with_rewards(ctx: Context<UnstakeWithRewards>, amount: u64) -> Result<()> {
+        unstake_with_rewards::handler(ctx, amount)
+    }
+
+    /// Deposits yiTokens into a [RewardPosition].
+    #[access_control(ctx.accounts.validate())]
+    pub fn deposit_to_reward_position(
+        ctx: Context<DepositToRewardPosition>,
+        amount: u64,
+    ) -> Result<()> {
+        deposit_to_reward_position::handler(ctx, amount)
+    }
+
+    /// Withdraws yiTokens from a [RewardPosition].
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_from_reward_position(
+        ctx: Context<WithdrawFromRewardPosition>,
+        amount: u64,
+    ) -> Result<()> {
+        withdraw_from_reward_position::handler(ctx, amount)
+    }
+
+    /// Claims the rewards earned by a [RewardPosition].
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        claim_rewards::handler(ctx)
+    }
+
+    /// Creates an [EmissionSchedule] which emits underlying tokens into a [YiToken].
+    /// Only callable by the [YiToken::admin].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_emission_schedule(
+        ctx: Context<CreateEmissionSchedule>,
+        emissions_per_second: u64,
+        end_ts: i64,
+        bounty_millibps: u32,
+    ) -> Result<()> {
+        create_emission_schedule::handler(ctx, emissions_per_second, end_ts, bounty_millibps)
+    }
+
+    /// Moves the underlying tokens emitted since the last crank into the [YiToken].
+    /// Anyone may call this; the caller receives [EmissionSchedule::bounty_millibps] of the emission.
+    #[access_control(ctx.accounts.validate())]
+    pub fn crank_emissions(ctx: Context<CrankEmissions>) -> Result<()> {
+        crank_emissions::handler(ctx)
+    }
+
+    /// Cranks pending emissions, then changes the emission rate and end time of an [EmissionSchedule].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_emission_schedule(
+        ctx: Context<SetEmissionSchedule>,
+        emissions_per_second: u64,
+        end_ts: i64,
+    ) -> Result<()> {
+        set_emission_schedule::handler(ctx, emissions_per_second, end_ts)
+    }
+
+    /// Registers a [HarvestAdapter] program from which yield may be harvested.
+    /// Only the [YiToken::admin] may register adapters.
+    #[access_control(ctx.accounts.validate())]
+    pub fn register_harvest_adapter(ctx: Context<RegisterHarvestAdapter>) -> Result<()> {
+        register_harvest_adapter::handler(ctx)
+    }
+
+    /// Harvests yield from a [HarvestAdapter] into the [YiToken]'s underlying tokens.
+    ///
+    /// The adapter program is invoked with the `global:harvest` Anchor discriminator as instruction data,
+    /// [YiToken::underlying_tokens] as its first account, and the remaining accounts after it.
+    /// It must transfer the yield into [YiToken::underlying_tokens] and report the amount
+    /// harvested as a little-endian `u64` via return data.
+    #[access_control(ctx.accounts.validate())]
+    pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>) -> Result<()> {
+        harvest::handler(ctx)
+    }
+
+    /// Registers the [Strategy] of a [YiToken]. Only the [YiToken::admin] may register it.
+    ///
+    /// [Strategy::strategy_tokens] must be owned by the PDA of the strategy program with seeds
+    /// [STRATEGY_AUTHORITY_SEED] and the [YiToken].
+    #[access_control(ctx.accounts.validate())]
+    pub fn register_strategy(
+        ctx: Context<RegisterStrategy>,
+        max_deployed_millibps: u32,
+    ) -> Result<()> {
+        register_strategy::handler(ctx, max_deployed_millibps)
+    }
+
+    /// Sets [Strategy::max_deployed_millibps]. Takes effect on the next [yi::rebalance].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_strategy_ratio(
+        ctx: Context<SetStrategyRatio>,
+        max_deployed_millibps: u32,
+    ) -> Result<()> {
+        set_strategy_ratio::handler(ctx, max_deployed_millibps)
+    }
+
+    /// Deploys idle underlying tokens to, or recalls them from, the [Strategy]
+    /// so that [Strategy::max_deployed_millibps] of the pool is deployed.
+    ///
+    /// Deposits invoke the strategy's `global:deposit(amount)` instruction after transferring
+    /// the tokens to [Strategy::strategy_tokens], passing that account first.
+    /// Recalls invoke `global:withdraw(amount)`, passing [YiToken::underlying_tokens] first;
+    /// the strategy must transfer exactly `amount` into it.
+    /// Remaining accounts are passed through to the strategy program.
+    ///
+    /// Any shortfall of [Strategy::strategy_tokens] below [YiToken::strategy_deployed] is first
+    /// realized as a loss in the exchange rate.
+    #[access_control(ctx.accounts.validate())]
+    pub fn rebalance<'info>(ctx: Context<'_, '_, '_, 'info, Rebalance<'info>>) -> Result<()> {
+        rebalance::handler(ctx)
+    }
+
+    /// Unstakes yiTokens, first recalling from the [Strategy] whatever the liquidity buffer is short.
+    /// Remaining accounts are passed through to the strategy program.
+    ///
+    /// Like [yi::rebalance], any loss of the [Strategy] is realized before unstaking.
+    ///
+    /// If `amount` is [ALL_TOKENS], the full balance of the source account is unstaked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn unstake_with_recall<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeWithRecall<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        unstake_with_recall::handler(ctx, amount)
+    }
+
+    /// Registers a [SwapRoute] for compounding tokens of another mint. Only the [YiToken::admin] may register routes.
+    #[access_control(ctx.accounts.validate())]
+    pub fn register_swap_route(ctx: Context<RegisterSwapRoute>) -> Result<()> {
+        register_swap_route::handler(ctx)
+    }
+
+    /// Swaps the entire [SwapRoute::side_vault] into the underlying token and compounds the output.
+    /// Only callable by the [YiToken::admin].
+    ///
+    /// The swap program is invoked with the `global:swap(amount_in: u64, min_amount_out: u64)`
+    /// Anchor instruction, passing [SwapRoute::side_vault], the [SwapRoute] as its signing authority,
+    /// and [YiToken::underlying_tokens] as the first three accounts, followed by the remaining accounts.
+    #[access_control(ctx.accounts.validate())]
+    pub fn compound_from<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompoundFrom<'info>>,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        compound_from::handler(ctx, min_amount_out)
+    }
+
+    /// Creates an [Escrow] for locking yiTokens in exchange for voting power.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_escrow(ctx: Context<CreateEscrow>) -> Result<()> {
+        create_escrow::handler(ctx)
+    }
+
+    /// Locks yiTokens in an [Escrow] until `unlock_at`, which may not shorten the current lock.
+    #[access_control(ctx.accounts.validate())]
+    pub fn lock(ctx: Context<Lock>, amount: u64, unlock_at: i64) -> Result<()> {
+        lock::handler(ctx, amount, unlock_at)
+    }
+
+    /// Extends the lock of an [Escrow] to `unlock_at`.
+    #[access_control(ctx.accounts.validate())]
+    pub fn extend_lock(ctx: Context<ExtendLock>, unlock_at: i64) -> Result<()> {
+        extend_lock::handler(ctx, unlock_at)
+    }
+
+    /// Withdraws all yiTokens from an [Escrow] whose lock has expired.
+    #[access_control(ctx.accounts.validate())]
+    pub fn exit(ctx: Context<Exit>) -> Result<()> {
+        exit::handler(ctx)
+    }
+
+    /// Creates a [Registrar] providing voter weight for an SPL Governance realm from
+    /// deposited yiTokens. Only the realm authority may create it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_registrar(ctx: Context<CreateRegistrar>) -> Result<()> {
+        create_registrar::handler(ctx)
+    }
+
+    /// Creates a [Voter] and its [VoterWeightRecord].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_voter(ctx: Context<CreateVoter>) -> Result<()> {
+        create_voter::handler(ctx)
+    }
+
+    /// Deposits yiTokens into a [Voter].
+    #[access_control(ctx.accounts.validate())]
+    pub fn deposit_to_voter(ctx: Context<DepositToVoter>, amount: u64) -> Result<()> {
+        deposit_to_voter::handler(ctx, amount)
+    }
+
+    /// Withdraws yiTokens from a [Voter], clearing its voter weight until it is updated.
+    /// The owner must first relinquish every vote recorded in its SPL Governance token owner record.
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_from_voter(ctx: Context<WithdrawFromVoter>, amount: u64) -> Result<()> {
+        withdraw_from_voter::handler(ctx, amount)
+    }
+
+    /// Writes the underlying value of the yiTokens deposited into a [Voter] to its [VoterWeightRecord].
+    ///
+    /// The record expires at the end of the current slot, so this must be invoked in the same
+    /// transaction as the governance instruction using it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn update_voter_weight_record(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
+        update_voter_weight_record::handler(ctx)
+    }
+
+    /// Creates a [Term] for splitting yiTokens into principal and yield tokens maturing at `maturity_ts`.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_term(ctx: Context<CreateTerm>, maturity_ts: i64) -> Result<()> {
+        create_term::handler(ctx, maturity_ts)
+    }
+
+    /// Splits yiTokens into equal amounts of principal and yield tokens of a [Term] before it matures.
+    #[access_control(ctx.accounts.validate())]
+    pub fn split(ctx: Context<Split>, amount: u64) -> Result<()> {
+        split::handler(ctx, amount)
+    }
+
+    /// Merges equal amounts of principal and yield tokens of a [Term] back into yiTokens.
+    #[access_control(ctx.accounts.validate())]
+    pub fn merge(ctx: Context<Merge>, amount: u64) -> Result<()> {
+        merge::handler(ctx, amount)
+    }
+
+    /// Redeems principal tokens of a matured [Term] for yiTokens.
+    #[access_control(ctx.accounts.validate())]
+    pub fn redeem_principal(ctx: Context<RedeemPrincipal>, amount: u64) -> Result<()> {
+        redeem_principal::handler(ctx, amount)
+    }
+
+    /// Redeems yield tokens of a matured [Term] for yiTokens.
+    #[access_control(ctx.accounts.validate())]
+    pub fn redeem_yield(ctx: Context<RedeemYield>, amount: u64) -> Result<()> {
+        redeem_yield::handler(ctx, amount)
+    }
+
+    /// Fixes [Term::maturity_rate] at the current [YiToken::last_rate] once the [Term] has matured.
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_term(ctx: Context<SettleTerm>) -> Result<()> {
+        settle_term::handler(ctx)
+    }
 }
 
 /// Errors.
@@ -101,4 +738,84 @@ pub enum ErrorCode {
     InvalidStakeFee,
     #[msg("Unstake fee cannot exceed 100%.")]
     InvalidUnstakeFee,
+    #[msg("Cannot compound rewards into a pool with no staked tokens.")]
+    NoStakedTokens,
+    #[msg("Underlying tokens are less than the amount accounted for by the pool.")]
+    UnderlyingTokensMismatch,
+    #[msg("A flash loan is already in progress.")]
+    FlashLoanInProgress,
+    #[msg(
+        "Flash loans must be repaid by a later flash_repay instruction in the same transaction."
+    )]
+    FlashLoanNotRepaid,
+    #[msg("Flash loans cannot be taken via CPI.")]
+    FlashLoanCPINotAllowed,
+    #[msg("There is no outstanding flash loan to repay.")]
+    NoFlashLoan,
+    #[msg("Emission bounty exceeds the maximum.")]
+    InvalidEmissionBounty,
+    #[msg("The harvest adapter did not report the amount harvested.")]
+    HarvestAmountNotReported,
+    #[msg("The amount reported by the harvest adapter does not match the vault balance increase.")]
+    HarvestAmountMismatch,
+    #[msg("Strategy ratio cannot exceed 95%.")]
+    InvalidStrategyRatio,
+    #[msg("The strategy did not return the amount recalled.")]
+    StrategyRecallMismatch,
+    #[msg("Not enough underlying tokens are liquid. Funds must be recalled from the strategy.")]
+    InsufficientLiquidity,
+    #[msg("Swap output is below the minimum amount out.")]
+    SwapOutputTooLow,
+    #[msg("The underlying token of this YiToken is not wrapped SOL.")]
+    UnderlyingNotNativeMint,
+    #[msg("Each amount in a batch must have a pair of remaining accounts.")]
+    BatchAccountsMismatch,
+    #[msg("Migration would mint fewer yiTokens than the minimum amount out.")]
+    MigrationOutputTooLow,
+    #[msg("Locks may not end in the past or before the current unlock time.")]
+    EscrowLockTooShort,
+    #[msg("Lock duration exceeds the maximum.")]
+    EscrowLockTooLong,
+    #[msg("Escrow is still locked.")]
+    EscrowLocked,
+    #[msg("Realm is not a valid SPL Governance realm for this mint.")]
+    InvalidRealm,
+    #[msg("Maturity must be in the future.")]
+    InvalidMaturity,
+    #[msg("Term has matured.")]
+    TermMatured,
+    #[msg("Term has not matured.")]
+    TermNotMatured,
+    #[msg("Pool has not matured and does not allow early exits.")]
+    PoolNotMatured,
+    #[msg("Staking into this pool has closed.")]
+    StakingClosed,
+    #[msg("Early exit penalty must be at most 100%.")]
+    InvalidEarlyExitPenalty,
+    #[msg("Cannot unstake more yiTokens than the stake receipt holds.")]
+    ReceiptBalanceExceeded,
+    #[msg("Referral fee share must be at most 100%.")]
+    InvalidReferralFeeShare,
+    #[msg("Authority is not exempt from fees.")]
+    NotFeeExempt,
+    #[msg("Outflow limit reached for this window. See the logs for when capacity resets.")]
+    OutflowLimitExceeded,
+    #[msg("Outflow window must be positive.")]
+    InvalidOutflowWindow,
+    #[msg("Pool is paused.")]
+    PoolPaused,
+    #[msg("Amount must be positive.")]
+    ZeroAmount,
+    #[msg("Token owner record is not a valid SPL Governance token owner record.")]
+    InvalidTokenOwnerRecord,
+    #[msg("Voter must relinquish all votes before withdrawing.")]
+    VoterHasActiveVotes,
+    #[msg("YiToken has already been migrated.")]
+    AlreadyMigrated,
+    #[msg("Stake would mint no yiTokens.")]
+    NothingMinted,
+    #[msg("Stake would mint fewer yiTokens than the minimum amount out.")]
+    StakeOutputTooLow,
+    #[msg("YiToken has no admin.")]
+    NoAdmin,
 }
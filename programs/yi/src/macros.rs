@@ -11,3 +11,105 @@ macro_rules! yitoken_seeds {
         ]]
     };
 }
+
+/// Generates the signer seeds for an [crate::EmissionSchedule].
+#[macro_export]
+macro_rules! emission_schedule_seeds {
+    ($schedule: expr) => {
+        &[&[
+            b"EmissionSchedule" as &[u8],
+            &$schedule.yi_token.to_bytes(),
+            &[$schedule.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::SwapRoute].
+#[macro_export]
+macro_rules! swap_route_seeds {
+    ($route: expr) => {
+        &[&[
+            b"SwapRoute" as &[u8],
+            &$route.yi_token.to_bytes(),
+            &$route.input_mint.to_bytes(),
+            &[$route.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::RewardStream].
+#[macro_export]
+macro_rules! reward_stream_seeds {
+    ($stream: expr) => {
+        &[&[
+            b"RewardStream" as &[u8],
+            &$stream.yi_token.to_bytes(),
+            &$stream.reward_mint.to_bytes(),
+            &[$stream.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::RewardPosition].
+#[macro_export]
+macro_rules! reward_position_seeds {
+    ($position: expr) => {
+        &[&[
+            b"RewardPosition" as &[u8],
+            &$position.stream.to_bytes(),
+            &$position.owner.to_bytes(),
+            &[$position.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for an [crate::Escrow].
+#[macro_export]
+macro_rules! escrow_seeds {
+    ($escrow: expr) => {
+        &[&[
+            b"Escrow" as &[u8],
+            &$escrow.yi_token.to_bytes(),
+            &$escrow.owner.to_bytes(),
+            &[$escrow.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::Voter].
+#[macro_export]
+macro_rules! voter_seeds {
+    ($voter: expr) => {
+        &[&[
+            b"Voter" as &[u8],
+            &$voter.registrar.to_bytes(),
+            &$voter.owner.to_bytes(),
+            &[$voter.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::Term].
+#[macro_export]
+macro_rules! term_seeds {
+    ($term: expr) => {
+        &[&[
+            b"Term" as &[u8],
+            &$term.yi_token.to_bytes(),
+            &$term.maturity_ts.to_le_bytes(),
+            &[$term.bump],
+        ]]
+    };
+}
+
+/// Generates the signer seeds for a [crate::Position].
+#[macro_export]
+macro_rules! position_seeds {
+    ($position: expr) => {
+        &[&[
+            b"Position" as &[u8],
+            &$position.mint.to_bytes(),
+            &[$position.bump],
+        ]]
+    };
+}
@@ -3,6 +3,7 @@
 #![deny(clippy::integer_arithmetic)]
 
 use anchor_lang::solana_program::pubkey::PUBKEY_BYTES;
+use anchor_lang::Discriminator;
 use num_traits::ToPrimitive;
 
 use crate::*;
@@ -29,11 +30,213 @@ pub struct YiToken {
     pub stake_fee_millibps: u32,
     /// The unstaking fee in thousands of bps.
     pub unstake_fee_millibps: u32,
+
+    /// Total number of underlying tokens accounted for by the pool.
+    ///
+    /// Tokens sent directly to [YiToken::underlying_tokens] are not counted
+    /// until they are recognized via [crate::yi::sync].
+    pub total_underlying: u64,
+
+    /// Total number of underlying tokens ever staked into the pool.
+    pub total_staked: u64,
+    /// Total number of underlying tokens ever unstaked from the pool.
+    pub total_unstaked: u64,
+    /// Total stake fees collected by the pool, in underlying tokens.
+    pub total_stake_fees: u64,
+    /// Total unstake fees collected by the pool, in underlying tokens.
+    pub total_unstake_fees: u64,
+    /// Total number of underlying tokens ever compounded into the pool as rewards.
+    pub total_rewards_deposited: u64,
+    /// Total number of stakes and unstakes performed on the pool.
+    pub num_operations: u64,
+
+    /// Exchange rate after the most recent operation on the pool, scaled by [RATE_PRECISION].
+    ///
+    /// This is updated by every instruction which changes the exchange rate, so consumers
+    /// which need the current rate as plain account data should read it here.
+    pub last_rate: u64,
+    /// Sum of [YiToken::last_rate] multiplied by the number of seconds it was in effect.
+    pub rate_cumulative: u128,
+    /// Timestamp of the last update to [YiToken::rate_cumulative].
+    pub rate_last_updated_at: i64,
+
+    /// Number of underlying tokens lent out by an outstanding flash loan.
+    pub flash_loan_amount: u64,
+
+    /// Account which may configure the [YiToken]'s integrations.
+    /// [Pubkey::default] if the [YiToken] has no admin.
+    pub admin: Pubkey,
+
+    /// Underlying tokens deployed to the [Strategy]. These are still counted in
+    /// [YiToken::total_underlying], but are not held in [YiToken::underlying_tokens].
+    pub strategy_deployed: u64,
+
+    /// When the pool matures. Before then, unstakes are rejected unless
+    /// [YiToken::early_exit_penalty_millibps] is set. Zero if the pool never matures.
+    pub maturity_ts: i64,
+    /// Time after which stakes are rejected. Zero if staking never closes.
+    pub stake_deadline_ts: i64,
+    /// Penalty on unstakes before [YiToken::maturity_ts] in thousands of bps, which compounds to
+    /// the remaining holders. Zero if early unstakes are rejected.
+    pub early_exit_penalty_millibps: u32,
+    /// Share of the stake fee paid to the referrer of a stake via [crate::yi::stake_with_referral],
+    /// in thousands of bps.
+    pub referral_fee_share_millibps: u32,
+
+    /// Maximum underlying tokens which may be unstaked per [YiToken::outflow_window_seconds].
+    /// Zero if outflows are unlimited.
+    pub outflow_limit: u64,
+    /// Length of an outflow window, in seconds.
+    pub outflow_window_seconds: i64,
+    /// When the current outflow window started.
+    pub outflow_window_start: i64,
+    /// Underlying tokens unstaked in the current outflow window.
+    pub outflow_in_window: u64,
+
+    /// Maximum increase of [YiToken::last_rate] within a second, in thousands of bps, before the
+    /// circuit breaker pauses the pool. Zero if the circuit breaker is disabled.
+    pub rate_jump_limit_millibps: u32,
+    /// Non-zero if the pool is paused, rejecting stakes and unstakes.
+    pub is_paused: u8,
+    /// Padding.
+    pub _padding2: [u8; 3],
+    /// [YiToken::last_rate] as of the start of [YiToken::rate_checkpoint_at].
+    pub rate_checkpoint: u64,
+    /// Timestamp of [YiToken::rate_checkpoint].
+    pub rate_checkpoint_at: i64,
+    /// Padding.
+    pub _padding3: [u8; 8],
 }
 
 impl YiToken {
+    /// Number of bytes in a [YiToken] created before [YiToken::total_underlying] was tracked.
+    /// Such accounts must be resized via [crate::yi::migrate_yi_token] before use.
+    pub const LEGACY_SIZE: usize = PUBKEY_BYTES + 1 + 7 + PUBKEY_BYTES * 2 + 4 + 4;
+
     /// Number of bytes in a [YiToken].
-    pub const SIZE: usize = PUBKEY_BYTES + 1 + 7 + PUBKEY_BYTES * 2 + 4 + 4;
+    pub const SIZE: usize = PUBKEY_BYTES
+        + 1
+        + 7
+        + PUBKEY_BYTES * 2
+        + 4
+        + 4
+        + 8
+        + 8 * 6
+        + 8
+        + 16
+        + 8
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + 8
+        + 8
+        + 4
+        + 4
+        + 8 * 4
+        + 4
+        + 1
+        + 3
+        + 8
+        + 8
+        + 8;
+
+    /// Records `amount` underlying tokens unstaked at `now` against [YiToken::outflow_limit],
+    /// starting a new window if the current one has ended.
+    ///
+    /// Returns false, recording nothing, if the unstake would exceed the limit.
+    pub fn record_outflow(&mut self, amount: u64, now: i64) -> Option<bool> {
+        if self.outflow_limit == 0 {
+            return Some(true);
+        }
+        if now >= self.outflow_window_end()? {
+            self.outflow_window_start = now;
+            self.outflow_in_window = 0;
+        }
+        let outflow_in_window = self.outflow_in_window.checked_add(amount)?;
+        if outflow_in_window > self.outflow_limit {
+            return Some(false);
+        }
+        self.outflow_in_window = outflow_in_window;
+        Some(true)
+    }
+
+    /// When the current outflow window ends and the full [YiToken::outflow_limit] is available again.
+    pub fn outflow_window_end(&self) -> Option<i64> {
+        self.outflow_window_start
+            .checked_add(self.outflow_window_seconds)
+    }
+
+    /// Returns true if [YiToken::last_rate] has fallen below [YiToken::rate_checkpoint] or has risen
+    /// more than [YiToken::rate_jump_limit_millibps] above it within the second `now`.
+    ///
+    /// The rate only moves with [YiToken::total_underlying], so tokens sent directly to the pool
+    /// cannot trip the breaker.
+    pub fn has_rate_jumped(&self, now: i64) -> Option<bool> {
+        if self.rate_jump_limit_millibps == 0 || now != self.rate_checkpoint_at {
+            return Some(false);
+        }
+        if self.last_rate < self.rate_checkpoint {
+            return Some(true);
+        }
+        let max_rate = (self.rate_checkpoint as u128)
+            .checked_mul(
+                u128::from(MILLIBPS_PER_WHOLE).checked_add(self.rate_jump_limit_millibps.into())?,
+            )?
+            .checked_div(MILLIBPS_PER_WHOLE.into())?;
+        Some(u128::from(self.last_rate) > max_rate)
+    }
+
+    /// Pauses the pool if [YiToken::has_rate_jumped] at `now`, returning true if it was paused.
+    pub fn trip_circuit_breaker(&mut self, now: i64) -> Option<bool> {
+        if self.is_paused != 0 || !self.has_rate_jumped(now)? {
+            return Some(false);
+        }
+        self.is_paused = 1;
+        Some(true)
+    }
+
+    /// Returns true if underlying tokens may be staked at `now`.
+    pub fn can_stake(&self, now: i64) -> bool {
+        self.stake_deadline_ts == 0 || now <= self.stake_deadline_ts
+    }
+
+    /// Returns true if [YiToken]s may be unstaked at `now`.
+    pub fn can_unstake(&self, now: i64) -> bool {
+        now >= self.maturity_ts || self.early_exit_penalty_millibps > 0
+    }
+
+    /// Returns a copy of this [YiToken] which charges no stake or unstake fees if `waived`,
+    /// for computing the fees of a [FeeExemption] holder.
+    pub fn with_fees_waived(&self, waived: bool) -> Self {
+        if waived {
+            Self {
+                stake_fee_millibps: 0,
+                unstake_fee_millibps: 0,
+                ..*self
+            }
+        } else {
+            *self
+        }
+    }
+
+    /// Calculates the share of a stake fee of `fee` underlying tokens paid to a referrer.
+    pub fn calculate_referral_rebate(&self, fee: u64) -> Option<u64> {
+        (fee as u128)
+            .checked_mul(self.referral_fee_share_millibps.into())?
+            .checked_div(MILLIBPS_PER_WHOLE.into())?
+            .to_u64()
+    }
+
+    /// Calculates the early exit penalty, in underlying tokens, deducted from `withdraw_amount` at `now`.
+    pub fn calculate_early_exit_penalty(&self, withdraw_amount: u64, now: i64) -> Option<u64> {
+        if now >= self.maturity_ts {
+            return Some(0);
+        }
+        (withdraw_amount as u128)
+            .checked_mul(self.early_exit_penalty_millibps.into())?
+            .checked_div(MILLIBPS_PER_WHOLE.into())?
+            .to_u64()
+    }
 
     /// Calculates the number of [YiToken::underlying_token_mint] tokens to mint for the given amount of [YiToken]s.
     pub fn calculate_underlying_for_yitokens(
@@ -94,54 +297,1688 @@ impl YiToken {
                 .to_u64()
         }
     }
+
+    /// Calculates the stake fee, in underlying tokens, paid when staking `underlying_amount` tokens
+    /// for `yitoken_amount` [YiToken]s.
+    pub fn calculate_stake_fee(
+        &self,
+        underlying_amount: u64,
+        yitoken_amount: u64,
+        total_underlying_tokens: u64,
+        total_supply: u64,
+    ) -> Option<u64> {
+        // no fee is taken on the initial 1:1 stake
+        if self.stake_fee_millibps == 0 || total_underlying_tokens == 0 || total_supply == 0 {
+            return Some(0);
+        }
+        let value_no_fee = (yitoken_amount as u128)
+            .checked_mul(total_underlying_tokens.into())?
+            .checked_div(total_supply.into())?
+            .to_u64()?;
+        underlying_amount.checked_sub(value_no_fee)
+    }
+
+    /// Calculates the unstake fee, in underlying tokens, paid when unstaking `yitoken_amount` [YiToken]s
+    /// for `underlying_amount` tokens.
+    pub fn calculate_unstake_fee(
+        &self,
+        yitoken_amount: u64,
+        underlying_amount: u64,
+        total_underlying_tokens: u64,
+        total_supply: u64,
+    ) -> Option<u64> {
+        // no fee is taken when withdrawing the entire supply
+        if self.unstake_fee_millibps == 0 || yitoken_amount == total_supply {
+            return Some(0);
+        }
+        let amt_no_fee = (yitoken_amount as u128)
+            .checked_mul(total_underlying_tokens.into())?
+            .checked_div(total_supply.into())?
+            .to_u64()?;
+        amt_no_fee.checked_sub(underlying_amount)
+    }
+
+    /// Records a stake of `underlying_amount` tokens into the pool.
+    pub fn record_stake(&mut self, underlying_amount: u64, fee: u64) -> Option<()> {
+        self.total_underlying = self.total_underlying.checked_add(underlying_amount)?;
+        self.total_staked = self.total_staked.checked_add(underlying_amount)?;
+        self.total_stake_fees = self.total_stake_fees.checked_add(fee)?;
+        self.num_operations = self.num_operations.checked_add(1)?;
+        Some(())
+    }
+
+    /// Records an unstake of `underlying_amount` tokens from the pool.
+    pub fn record_unstake(&mut self, underlying_amount: u64, fee: u64) -> Option<()> {
+        self.total_underlying = self.total_underlying.checked_sub(underlying_amount)?;
+        self.total_unstaked = self.total_unstaked.checked_add(underlying_amount)?;
+        self.total_unstake_fees = self.total_unstake_fees.checked_add(fee)?;
+        self.num_operations = self.num_operations.checked_add(1)?;
+        Some(())
+    }
+
+    /// Records `amount` underlying tokens compounded into the pool as rewards.
+    pub fn record_rewards(&mut self, amount: u64) -> Option<()> {
+        self.total_underlying = self.total_underlying.checked_add(amount)?;
+        self.total_rewards_deposited = self.total_rewards_deposited.checked_add(amount)?;
+        Some(())
+    }
+
+    /// Writes [YiToken::strategy_deployed] down to `value`, the balance of
+    /// [Strategy::strategy_tokens], realizing the loss against [YiToken::total_underlying].
+    ///
+    /// Gains are not recognized, since anyone may transfer tokens to the strategy;
+    /// they must be harvested instead. Returns the loss realized.
+    pub fn realize_strategy_loss(&mut self, value: u64) -> Option<u64> {
+        if value >= self.strategy_deployed {
+            return Some(0);
+        }
+        let loss = self.strategy_deployed.checked_sub(value)?;
+        self.strategy_deployed = value;
+        self.total_underlying = self.total_underlying.checked_sub(loss)?;
+        Some(loss)
+    }
+
+    /// Calculates the fee charged for a flash loan of `amount` underlying tokens, rounded up.
+    pub fn calculate_flash_loan_fee(amount: u64) -> Option<u64> {
+        let whole: u128 = MILLIBPS_PER_WHOLE.into();
+        (amount as u128)
+            .checked_mul(FLASH_LOAN_FEE_MILLIBPS.into())?
+            .checked_add(whole.checked_sub(1)?)?
+            .checked_div(whole)?
+            .to_u64()
+    }
+
+    /// Calculates the number of underlying tokens per [YiToken], scaled by [RATE_PRECISION].
+    pub fn calculate_exchange_rate(&self, total_supply: u64) -> Option<u64> {
+        // an empty pool is 1:1
+        if total_supply == 0 {
+            return Some(RATE_PRECISION);
+        }
+        (self.total_underlying as u128)
+            .checked_mul(RATE_PRECISION.into())?
+            .checked_div(total_supply.into())?
+            .to_u64()
+    }
+
+    /// Computes what [YiToken::rate_cumulative] would be if it were updated at `now`.
+    pub fn current_rate_cumulative(&self, now: i64) -> Option<u128> {
+        let elapsed = now.checked_sub(self.rate_last_updated_at)?.to_u64()?;
+        let delta = (self.last_rate as u128).checked_mul(elapsed.into())?;
+        self.rate_cumulative.checked_add(delta)
+    }
+
+    /// Accumulates the previous rate up to `now`, then records the rate for the new `total_supply`.
+    ///
+    /// This must be called after every change to [YiToken::total_underlying] or the supply.
+    /// The first update within a second moves [YiToken::rate_checkpoint] to the previous rate.
+    pub fn update_rate(&mut self, total_supply: u64, now: i64) -> Option<()> {
+        if now != self.rate_checkpoint_at {
+            self.rate_checkpoint = self.last_rate;
+            self.rate_checkpoint_at = now;
+        }
+        self.rate_cumulative = self.current_rate_cumulative(now)?;
+        self.rate_last_updated_at = now;
+        self.last_rate = self.calculate_exchange_rate(total_supply)?;
+        Some(())
+    }
 }
 
-#[cfg(test)]
-#[allow(clippy::unwrap_used, clippy::integer_arithmetic)]
-mod tests {
-    use std::mem::size_of;
+/// Stores the exchange rate of a [YiToken] as plain account data in its own account.
+///
+/// The rate is only refreshed by [crate::yi::refresh_rate] and by operations which are passed
+/// the [RateProvider] as a remaining account, so it may be stale. Consumers which require
+/// the current rate should read [YiToken::last_rate] instead, or check
+/// [RateProvider::last_updated_slot].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct RateProvider {
+    /// The [YiToken] whose rate is provided.
+    pub yi_token: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
 
-    use super::*;
-    use proptest::prelude::*;
+    /// Underlying tokens per [YiToken], scaled by [RATE_PRECISION].
+    pub rate: u64,
+    /// Slot at which [RateProvider::rate] was last updated.
+    pub last_updated_slot: u64,
+    /// Timestamp at which [RateProvider::rate] was last updated.
+    pub last_updated_at: i64,
+}
 
-    #[test]
-    fn test_yitoken_size() {
-        assert_eq!(YiToken::SIZE, size_of::<YiToken>());
+impl RateProvider {
+    /// Number of bytes in a [RateProvider].
+    pub const SIZE: usize = PUBKEY_BYTES + 1 + 7 + 8 + 8 + 8;
+
+    /// Sets the rate as of the given slot and timestamp.
+    pub fn refresh(&mut self, rate: u64, slot: u64, timestamp: i64) {
+        self.rate = rate;
+        self.last_updated_slot = slot;
+        self.last_updated_at = timestamp;
     }
+}
 
-    #[test]
-    fn test_calculate_yitokens_for_underlying_init() {
-        let yi_token: YiToken = YiToken::default();
+/// Number of [RateObservation]s stored in a [RateOracle].
+pub const RATE_ORACLE_NUM_OBSERVATIONS: usize = 64;
 
-        let amount = yi_token
-            .calculate_yitokens_for_underlying(700_000, 0, 0)
-            .unwrap();
-        assert_eq!(amount, 700_000);
+/// A ring buffer of recent [YiToken::rate_cumulative] observations,
+/// used to compute time-weighted average exchange rates.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct RateOracle {
+    /// The [YiToken] being observed.
+    pub yi_token: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 15],
+
+    /// Index of the most recent observation in [RateOracle::observations].
+    pub latest_index: u64,
+    /// Number of observations recorded, up to [RATE_ORACLE_NUM_OBSERVATIONS].
+    pub num_observations: u64,
+    /// The observations.
+    pub observations: [RateObservation; RATE_ORACLE_NUM_OBSERVATIONS],
+}
+
+/// An observation of [YiToken::rate_cumulative] at a point in time.
+#[zero_copy]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RateObservation {
+    /// Time of the observation.
+    pub timestamp: i64,
+    /// Padding.
+    pub _padding: [u8; 8],
+    /// [YiToken::rate_cumulative] at [RateObservation::timestamp].
+    pub rate_cumulative: u128,
+}
+
+impl RateObservation {
+    /// Number of bytes in a [RateObservation].
+    pub const SIZE: usize = 8 + 8 + 16;
+
+    /// Calculates the time-weighted average exchange rate between this observation
+    /// and a later one, scaled by [RATE_PRECISION].
+    pub fn twap(&self, later: &RateObservation) -> Option<u64> {
+        let elapsed = later.timestamp.checked_sub(self.timestamp)?.to_u64()?;
+        later
+            .rate_cumulative
+            .checked_sub(self.rate_cumulative)?
+            .checked_div(elapsed.into())?
+            .to_u64()
     }
+}
 
-    #[test]
-    fn test_calculate_yitokens_for_underlying_no_fees() {
-        let yi_token: YiToken = YiToken::default();
-        let amount = yi_token
-            .calculate_yitokens_for_underlying(100_000, 700_000, 700_000)
-            .unwrap();
-        assert_eq!(amount, 100_000);
+impl RateOracle {
+    /// Number of bytes in a [RateOracle].
+    pub const SIZE: usize =
+        PUBKEY_BYTES + 1 + 15 + 8 + 8 + RateObservation::SIZE * RATE_ORACLE_NUM_OBSERVATIONS;
+
+    /// The most recent [RateObservation], if any.
+    pub fn latest(&self) -> Option<&RateObservation> {
+        if self.num_observations == 0 {
+            return None;
+        }
+        self.observations.get(self.latest_index.to_usize()?)
     }
 
-    #[test]
-    fn test_calculate_underlying_for_yitokens_init() {
-        let yi_token: YiToken = YiToken::default();
-        let amount = yi_token.calculate_underlying_for_yitokens(700_000, 0, 0);
-        assert_eq!(amount, None);
+    /// The oldest [RateObservation] still in the buffer, if any.
+    pub fn oldest(&self) -> Option<&RateObservation> {
+        if self.num_observations == 0 {
+            return None;
+        }
+        if self.num_observations.to_usize()? < RATE_ORACLE_NUM_OBSERVATIONS {
+            return self.observations.first();
+        }
+        self.observations.get(self.next_index()?)
     }
 
-    #[test]
-    fn test_calculate_underlying_for_yitokens_no_fees() {
-        let yi_token: YiToken = YiToken::default();
-        let amount = yi_token
-            .calculate_underlying_for_yitokens(100_000, 700_000, 700_000)
-            .unwrap();
-        assert_eq!(amount, 100_000);
+    /// Finds the most recent [RateObservation] recorded at or before `timestamp`.
+    pub fn observation_at_or_before(&self, timestamp: i64) -> Option<&RateObservation> {
+        let count = self.num_observations.to_usize()?;
+        let mut index = self.latest_index.to_usize()?;
+        for _ in 0..count {
+            let observation = self.observations.get(index)?;
+            if observation.timestamp <= timestamp {
+                return Some(observation);
+            }
+            index = index
+                .checked_add(RATE_ORACLE_NUM_OBSERVATIONS)?
+                .checked_sub(1)?
+                .checked_rem(RATE_ORACLE_NUM_OBSERVATIONS)?;
+        }
+        None
+    }
+
+    /// Records an observation, overwriting the oldest one if the buffer is full.
+    pub fn record(&mut self, observation: RateObservation) -> Option<()> {
+        let index = if self.num_observations == 0 {
+            0
+        } else {
+            self.next_index()?
+        };
+        *self.observations.get_mut(index)? = observation;
+        self.latest_index = index.to_u64()?;
+        if self.num_observations.to_usize()? < RATE_ORACLE_NUM_OBSERVATIONS {
+            self.num_observations = self.num_observations.checked_add(1)?;
+        }
+        Some(())
+    }
+
+    fn next_index(&self) -> Option<usize> {
+        self.latest_index
+            .to_usize()?
+            .checked_add(1)?
+            .checked_rem(RATE_ORACLE_NUM_OBSERVATIONS)
+    }
+}
+
+/// Streams a secondary reward token to [YiToken] holders who deposit their
+/// [YiToken]s into a [RewardPosition].
+///
+/// Only [YiToken]s held in a [RewardPosition] earn. Balances moved by the other stake and unstake
+/// instructions, such as [crate::yi::stake], are not settled against the stream.
+#[account]
+#[derive(Debug, Default)]
+pub struct RewardStream {
+    /// The [YiToken] whose holders are rewarded.
+    pub yi_token: Pubkey,
+    /// [anchor_spl::token::Mint] of the reward token.
+    pub reward_mint: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// Account which may change [RewardStream::rewards_per_second].
+    pub authority: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] holding the rewards to be distributed.
+    pub reward_tokens: Pubkey,
+
+    /// Number of reward tokens distributed per second across all [RewardPosition]s.
+    pub rewards_per_second: u64,
+    /// Total number of [YiToken]s deposited into the [RewardPosition]s of this stream.
+    pub total_shares: u64,
+    /// Rewards earned per [YiToken] since the creation of the stream,
+    /// scaled by [REWARD_PER_SHARE_PRECISION].
+    pub reward_per_share: u128,
+    /// Time at which [RewardStream::reward_per_share] was last updated.
+    pub last_update_ts: i64,
+}
+
+impl RewardStream {
+    /// Number of bytes in a [RewardStream].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + PUBKEY_BYTES * 2 + 8 + 8 + 16 + 8;
+
+    /// Accrues rewards to all shares up to `now`.
+    pub fn accrue(&mut self, now: i64) -> Option<()> {
+        if now <= self.last_update_ts {
+            return Some(());
+        }
+        if self.total_shares > 0 {
+            let elapsed = now.checked_sub(self.last_update_ts)?.to_u64()?;
+            let rewards = (self.rewards_per_second as u128).checked_mul(elapsed.into())?;
+            self.reward_per_share = self.reward_per_share.checked_add(
+                rewards
+                    .checked_mul(REWARD_PER_SHARE_PRECISION)?
+                    .checked_div(self.total_shares.into())?,
+            )?;
+        }
+        self.last_update_ts = now;
+        Some(())
+    }
+}
+
+/// A holder's deposit of [YiToken]s into a [RewardStream].
+#[account]
+#[derive(Debug, Default)]
+pub struct RewardPosition {
+    /// The [RewardStream] this position earns from.
+    pub stream: Pubkey,
+    /// Owner of the position.
+    pub owner: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+
+    /// The [anchor_spl::token::TokenAccount] holding the deposited [YiToken]s.
+    pub yi_tokens: Pubkey,
+
+    /// Number of [YiToken]s deposited.
+    pub shares: u64,
+    /// [RewardStream::reward_per_share] as of the last update to this position.
+    pub reward_per_share_paid: u128,
+    /// Rewards earned but not yet claimed.
+    pub rewards_earned: u64,
+}
+
+impl RewardPosition {
+    /// Number of bytes in a [RewardPosition].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + PUBKEY_BYTES + 8 + 16 + 8;
+
+    /// Credits the rewards earned since the last update. The stream must already be accrued.
+    pub fn update_rewards(&mut self, stream: &RewardStream) -> Option<()> {
+        let earned = (self.shares as u128)
+            .checked_mul(
+                stream
+                    .reward_per_share
+                    .checked_sub(self.reward_per_share_paid)?,
+            )?
+            .checked_div(REWARD_PER_SHARE_PRECISION)?
+            .to_u64()?;
+        self.rewards_earned = self.rewards_earned.checked_add(earned)?;
+        self.reward_per_share_paid = stream.reward_per_share;
+        Some(())
+    }
+
+    /// Adds `amount` [YiToken]s to the position.
+    pub fn deposit(&mut self, stream: &mut RewardStream, amount: u64) -> Option<()> {
+        self.shares = self.shares.checked_add(amount)?;
+        stream.total_shares = stream.total_shares.checked_add(amount)?;
+        Some(())
+    }
+
+    /// Removes `amount` [YiToken]s from the position.
+    pub fn withdraw(&mut self, stream: &mut RewardStream, amount: u64) -> Option<()> {
+        self.shares = self.shares.checked_sub(amount)?;
+        stream.total_shares = stream.total_shares.checked_sub(amount)?;
+        Some(())
+    }
+}
+
+/// A reserve of underlying tokens which is emitted into a [YiToken] at a fixed rate.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct EmissionSchedule {
+    /// The [YiToken] receiving the emissions.
+    pub yi_token: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Account which may change the emission rate and end time.
+    pub authority: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] holding the underlying tokens to be emitted.
+    pub reserve_tokens: Pubkey,
+
+    /// Number of underlying tokens emitted per second.
+    pub emissions_per_second: u64,
+    /// Time after which nothing is emitted.
+    pub end_ts: i64,
+    /// Time up to which emissions have been cranked.
+    pub last_crank_ts: i64,
+    /// Total underlying tokens emitted, including bounties.
+    pub total_emitted: u64,
+
+    /// Share of each crank paid to the cranker, in thousands of bps.
+    pub bounty_millibps: u32,
+    /// Padding.
+    pub _padding2: [u8; 4],
+}
+
+impl EmissionSchedule {
+    /// Number of bytes in an [EmissionSchedule].
+    pub const SIZE: usize = PUBKEY_BYTES + 1 + 7 + PUBKEY_BYTES * 2 + 8 * 4 + 4 + 4;
+
+    /// Calculates the amount emitted since the last crank, limited to the `reserve` balance.
+    /// Returns the amount and the time emissions are accounted for up to.
+    ///
+    /// Emissions which the reserve could not cover are skipped rather than carried over.
+    pub fn calculate_emission(&self, now: i64, reserve: u64) -> Option<(u64, i64)> {
+        let until = now.min(self.end_ts);
+        if until <= self.last_crank_ts {
+            return Some((0, self.last_crank_ts));
+        }
+        let elapsed = until.checked_sub(self.last_crank_ts)?.to_u64()?;
+        let accrued = (self.emissions_per_second as u128).checked_mul(elapsed.into())?;
+        let amount = accrued.min(reserve.into()).to_u64()?;
+        Some((amount, until))
+    }
+
+    /// Calculates the bounty paid to the cranker out of an emission of `amount`.
+    pub fn calculate_bounty(&self, amount: u64) -> Option<u64> {
+        (amount as u128)
+            .checked_mul(self.bounty_millibps.into())?
+            .checked_div(MILLIBPS_PER_WHOLE.into())?
+            .to_u64()
+    }
+}
+
+/// An external yield source registered by the [YiToken::admin], whose yield
+/// may be pulled into the [YiToken] via [crate::yi::harvest].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct HarvestAdapter {
+    /// The [YiToken] harvesting the yield.
+    pub yi_token: Pubkey,
+    /// The adapter program invoked to harvest.
+    pub program: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Total underlying tokens harvested via this adapter.
+    pub total_harvested: u64,
+    /// Time of the last harvest.
+    pub last_harvest_ts: i64,
+}
+
+impl HarvestAdapter {
+    /// Number of bytes in a [HarvestAdapter].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + 7 + 8 + 8;
+}
+
+/// A strategy program registered by the [YiToken::admin] which puts idle underlying tokens to work.
+///
+/// Up to [Strategy::max_deployed_millibps] of the pool may be deployed; the rest is
+/// kept in [YiToken::underlying_tokens] as a liquidity buffer for unstakes. Deployed tokens
+/// are valued at the balance of [Strategy::strategy_tokens].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Strategy {
+    /// The [YiToken] whose tokens are deployed.
+    pub yi_token: Pubkey,
+    /// The strategy program.
+    pub program: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] of the strategy receiving deployed tokens.
+    pub strategy_tokens: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 3],
+    /// Maximum share of [YiToken::total_underlying] to deploy, in thousands of bps.
+    /// At most [MAX_STRATEGY_RATIO_MILLIBPS].
+    pub max_deployed_millibps: u32,
+
+    /// Total underlying tokens ever deployed to the strategy.
+    pub total_deployed: u64,
+    /// Total underlying tokens ever recalled from the strategy.
+    pub total_recalled: u64,
+    /// Total losses of the strategy realized against [YiToken::total_underlying].
+    pub total_losses: u64,
+}
+
+impl Strategy {
+    /// Number of bytes in a [Strategy].
+    pub const SIZE: usize = PUBKEY_BYTES * 3 + 1 + 3 + 4 + 8 + 8 + 8;
+
+    /// Calculates the number of underlying tokens which should be deployed to the strategy.
+    pub fn calculate_target(&self, total_underlying: u64) -> Option<u64> {
+        (total_underlying as u128)
+            .checked_mul(self.max_deployed_millibps.into())?
+            .checked_div(MILLIBPS_PER_WHOLE.into())?
+            .to_u64()
+    }
+}
+
+/// A route registered by the [YiToken::admin] for swapping tokens of another mint
+/// into the underlying token via [crate::yi::compound_from].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct SwapRoute {
+    /// The [YiToken] compounding the swapped tokens.
+    pub yi_token: Pubkey,
+    /// Mint of the tokens swapped into the underlying token.
+    pub input_mint: Pubkey,
+    /// The swap program.
+    pub swap_program: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] owned by this route holding tokens to be swapped.
+    pub side_vault: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Total input tokens swapped.
+    pub total_amount_in: u64,
+    /// Total underlying tokens received from swaps.
+    pub total_amount_out: u64,
+}
+
+impl SwapRoute {
+    /// Number of bytes in a [SwapRoute].
+    pub const SIZE: usize = PUBKEY_BYTES * 4 + 1 + 7 + 8 + 8;
+}
+
+/// Locks [YiToken]s of an owner until [Escrow::unlock_at] in exchange for voting power.
+///
+/// The locked [YiToken]s continue to compound.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Escrow {
+    /// The [YiToken] whose tokens are locked.
+    pub yi_token: Pubkey,
+    /// Owner of the locked tokens.
+    pub owner: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] owned by this escrow holding the locked [YiToken]s.
+    pub tokens: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Number of [YiToken]s locked.
+    pub amount: u64,
+    /// When the current lock started.
+    pub escrow_started_at: i64,
+    /// When the locked tokens may be withdrawn.
+    pub unlock_at: i64,
+}
+
+impl Escrow {
+    /// Number of bytes in an [Escrow].
+    pub const SIZE: usize = PUBKEY_BYTES * 3 + 1 + 7 + 8 + 8 + 8;
+
+    /// Calculates the voting power of the escrow at `now`.
+    ///
+    /// This is the locked amount scaled by the remaining lock time over [MAX_ESCROW_LOCK_SECONDS],
+    /// so a maximum lock carries one vote per [YiToken].
+    pub fn voting_power(&self, now: i64) -> Option<u64> {
+        if now >= self.unlock_at {
+            return Some(0);
+        }
+        let remaining = self
+            .unlock_at
+            .checked_sub(now)?
+            .min(MAX_ESCROW_LOCK_SECONDS);
+        (self.amount as u128)
+            .checked_mul(remaining.to_u128()?)?
+            .checked_div(MAX_ESCROW_LOCK_SECONDS.to_u128()?)?
+            .to_u64()
+    }
+}
+
+/// Splits [YiToken]s into principal and yield tokens maturing at [Term::maturity_ts].
+///
+/// Both tokens are denominated in underlying tokens at [Term::start_rate], so every unit of either
+/// token carries the same claim regardless of when it was minted:
+/// - a principal token redeems for one underlying token's worth of [YiToken]s after maturity, and
+/// - a yield token redeems for the [YiToken]s by which one underlying token at [Term::start_rate]
+///   has grown in value by maturity.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Term {
+    /// The [YiToken] being split.
+    pub yi_token: Pubkey,
+    /// [anchor_spl::token::Mint] of the principal token.
+    pub principal_mint: Pubkey,
+    /// [anchor_spl::token::Mint] of the yield token.
+    pub yield_mint: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] owned by this term holding the split [YiToken]s.
+    pub yi_tokens: Pubkey,
+    /// When the term matures.
+    pub maturity_ts: i64,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// [YiToken::last_rate] when the term was created.
+    pub start_rate: u64,
+    /// [YiToken::last_rate] when the term was settled, but no less than [Term::start_rate].
+    /// Zero until the term is settled.
+    ///
+    /// The term is settled by [crate::yi::settle_term] or by the first redemption after
+    /// [Term::maturity_ts]. Yield compounded between maturity and settlement accrues to
+    /// the yield tokens, so the term should be settled as soon as it matures.
+    pub maturity_rate: u64,
+}
+
+impl Term {
+    /// Number of bytes in a [Term].
+    pub const SIZE: usize = PUBKEY_BYTES * 4 + 8 + 1 + 7 + 8 + 8;
+
+    /// Records `last_rate` as [Term::maturity_rate], if the term has not yet been settled.
+    pub fn settle(&mut self, last_rate: u64) {
+        if self.maturity_rate == 0 {
+            self.maturity_rate = last_rate.max(self.start_rate);
+        }
+    }
+
+    /// Calculates the number of principal and yield tokens minted for `yitoken_amount` [YiToken]s.
+    pub fn calculate_term_tokens_for_yitokens(&self, yitoken_amount: u64) -> Option<u64> {
+        (yitoken_amount as u128)
+            .checked_mul(self.start_rate.into())?
+            .checked_div(RATE_PRECISION.into())?
+            .to_u64()
+    }
+
+    /// Calculates the number of [YiToken]s returned for merging `amount` principal and yield tokens.
+    pub fn calculate_yitokens_for_term_tokens(&self, amount: u64) -> Option<u64> {
+        (amount as u128)
+            .checked_mul(RATE_PRECISION.into())?
+            .checked_div(self.start_rate.into())?
+            .to_u64()
+    }
+
+    /// Calculates the number of [YiToken]s `amount` principal tokens redeem for after settlement.
+    pub fn calculate_principal_redemption(&self, amount: u64) -> Option<u64> {
+        (amount as u128)
+            .checked_mul(RATE_PRECISION.into())?
+            .checked_div(self.maturity_rate.into())?
+            .to_u64()
+    }
+
+    /// Calculates the number of [YiToken]s `amount` yield tokens redeem for after settlement.
+    ///
+    /// Together with [Term::calculate_principal_redemption], this never exceeds
+    /// [Term::calculate_yitokens_for_term_tokens].
+    pub fn calculate_yield_redemption(&self, amount: u64) -> Option<u64> {
+        self.calculate_yitokens_for_term_tokens(amount)?
+            .checked_sub(self.calculate_principal_redemption(amount)?)
+    }
+}
+
+/// Binds a [YiToken] to an SPL Governance realm, allowing holders to vote with
+/// the underlying value of the [YiToken]s deposited into their [Voter].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Registrar {
+    /// The SPL Governance program which owns [Registrar::realm].
+    pub governance_program_id: Pubkey,
+    /// The realm.
+    pub realm: Pubkey,
+    /// The governing token mint of the realm whose voter weight is provided.
+    pub governing_token_mint: Pubkey,
+    /// The [YiToken] whose holders vote.
+    pub yi_token: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+}
+
+impl Registrar {
+    /// Number of bytes in a [Registrar].
+    pub const SIZE: usize = PUBKEY_BYTES * 4 + 1 + 7;
+}
+
+/// Holds the [YiToken]s an owner has deposited to vote via a [Registrar].
+///
+/// Deposited tokens are held by the program so that they cannot be counted by two voters.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Voter {
+    /// The [Registrar].
+    pub registrar: Pubkey,
+    /// Owner of the deposited tokens.
+    pub owner: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] owned by this voter holding the deposited [YiToken]s.
+    pub tokens: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+}
+
+impl Voter {
+    /// Number of bytes in a [Voter].
+    pub const SIZE: usize = PUBKEY_BYTES * 3 + 1 + 7;
+}
+
+/// The action a [VoterWeightRecord] is valid for, as defined by the SPL Governance addin API.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    /// Cast a vote.
+    CastVote,
+    /// Comment on a proposal.
+    CommentProposal,
+    /// Create a governance.
+    CreateGovernance,
+    /// Create a proposal.
+    CreateProposal,
+    /// Sign off a proposal.
+    SignOffProposal,
+}
+
+/// Voter weight read by SPL Governance, laid out as defined by its addin API.
+///
+/// SPL Governance checks the account discriminator against its own constant rather than
+/// the one Anchor would derive, so the account traits are implemented by hand.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct VoterWeightRecord {
+    /// The realm.
+    pub realm: Pubkey,
+    /// The governing token mint of the realm.
+    pub governing_token_mint: Pubkey,
+    /// The owner of the governing tokens, i.e. [Voter::owner].
+    pub governing_token_owner: Pubkey,
+    /// Voter weight, in underlying tokens.
+    pub voter_weight: u64,
+    /// The slot at which the weight was computed. The weight must be refreshed in the same slot it is used.
+    pub voter_weight_expiry: Option<u64>,
+    /// The action the weight is valid for, or any action if `None`.
+    pub weight_action: Option<VoterWeightAction>,
+    /// The target of [VoterWeightRecord::weight_action], if any.
+    pub weight_action_target: Option<Pubkey>,
+    /// Reserved.
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    /// Number of bytes in a [VoterWeightRecord].
+    pub const SIZE: usize = PUBKEY_BYTES * 3 + 8 + 1 + 8 + 1 + 1 + 1 + PUBKEY_BYTES + 8;
+
+    /// Discriminator SPL Governance expects, `VoterWeightRecord::ACCOUNT_DISCRIMINATOR` of its addin API.
+    pub const ACCOUNT_DISCRIMINATOR: [u8; 8] = *b"2ef99b4b";
+}
+
+impl Discriminator for VoterWeightRecord {
+    fn discriminator() -> [u8; 8] {
+        Self::ACCOUNT_DISCRIMINATOR
+    }
+}
+
+impl Owner for VoterWeightRecord {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl AccountSerialize for VoterWeightRecord {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        writer
+            .write_all(&Self::ACCOUNT_DISCRIMINATOR)
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotSerialize))?;
+        AnchorSerialize::serialize(self, writer)
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotSerialize))
+    }
+}
+
+impl AccountDeserialize for VoterWeightRecord {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() < Self::ACCOUNT_DISCRIMINATOR.len() {
+            return Err(error!(
+                anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound
+            ));
+        }
+        if buf[..8] != Self::ACCOUNT_DISCRIMINATOR {
+            return Err(error!(
+                anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+            ));
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data: &[u8] = &buf[8..];
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// A deposit into a [YiToken] tracked individually, represented by a one-of-one [Position::mint].
+///
+/// The [YiToken]s backing the position are held by the program and may only be redeemed by
+/// the holder of the position token.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Position {
+    /// The [YiToken] the position is in.
+    pub yi_token: Pubkey,
+    /// The one-of-one [anchor_spl::token::Mint] representing the position.
+    pub mint: Pubkey,
+    /// The [anchor_spl::token::TokenAccount] owned by this position holding its [YiToken]s.
+    pub tokens: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Number of [YiToken]s backing the position.
+    pub shares: u64,
+    /// Underlying tokens deposited for the remaining [Position::shares].
+    pub cost_basis: u64,
+    /// [YiToken::last_rate] when the position was opened.
+    pub entry_rate: u64,
+    /// When the position was opened.
+    pub entry_ts: i64,
+}
+
+impl Position {
+    /// Number of bytes in a [Position].
+    pub const SIZE: usize = PUBKEY_BYTES * 3 + 1 + 7 + 8 + 8 + 8 + 8;
+
+    /// Records the redemption of `shares`, reducing [Position::cost_basis] pro rata.
+    ///
+    /// Returns the cost basis of the redeemed shares.
+    pub fn record_redemption(&mut self, shares: u64) -> Option<u64> {
+        let redeemed_basis = if shares == self.shares {
+            self.cost_basis
+        } else {
+            (self.cost_basis as u128)
+                .checked_mul(shares.into())?
+                .checked_div(self.shares.into())?
+                .to_u64()?
+        };
+        self.shares = self.shares.checked_sub(shares)?;
+        self.cost_basis = self.cost_basis.checked_sub(redeemed_basis)?;
+        Some(redeemed_basis)
+    }
+}
+
+/// Opt-in record of an owner's deposits into and withdrawals from a [YiToken], used to report gains.
+///
+/// Only stakes and unstakes made via [crate::yi::stake_with_receipt] and
+/// [crate::yi::unstake_with_receipt] are recorded. Gains are inaccurate if the owner also
+/// stakes or unstakes via other instructions, such as [crate::yi::stake].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct StakeReceipt {
+    /// The [YiToken].
+    pub yi_token: Pubkey,
+    /// Owner of the receipt.
+    pub owner: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Total underlying tokens deposited.
+    pub total_deposited: u64,
+    /// Total underlying tokens withdrawn.
+    pub total_withdrawn: u64,
+    /// Number of [YiToken]s held through the recorded stakes.
+    pub yi_tokens: u64,
+    /// Underlying tokens deposited for the [StakeReceipt::yi_tokens] still held.
+    pub cost_basis: u64,
+}
+
+impl StakeReceipt {
+    /// Number of bytes in a [StakeReceipt].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + 7 + 8 * 4;
+
+    /// Records a stake of `underlying_amount` for `yitoken_amount` [YiToken]s.
+    pub fn record_stake(&mut self, underlying_amount: u64, yitoken_amount: u64) -> Option<()> {
+        self.total_deposited = self.total_deposited.checked_add(underlying_amount)?;
+        self.cost_basis = self.cost_basis.checked_add(underlying_amount)?;
+        self.yi_tokens = self.yi_tokens.checked_add(yitoken_amount)?;
+        Some(())
+    }
+
+    /// Records an unstake of `yitoken_amount` [YiToken]s for `underlying_amount`,
+    /// reducing [StakeReceipt::cost_basis] pro rata.
+    pub fn record_unstake(&mut self, yitoken_amount: u64, underlying_amount: u64) -> Option<()> {
+        let redeemed_basis = if yitoken_amount == self.yi_tokens {
+            self.cost_basis
+        } else {
+            (self.cost_basis as u128)
+                .checked_mul(yitoken_amount.into())?
+                .checked_div(self.yi_tokens.into())?
+                .to_u64()?
+        };
+        self.yi_tokens = self.yi_tokens.checked_sub(yitoken_amount)?;
+        self.cost_basis = self.cost_basis.checked_sub(redeemed_basis)?;
+        self.total_withdrawn = self.total_withdrawn.checked_add(underlying_amount)?;
+        Some(())
+    }
+
+    /// Calculates the realized gain on withdrawn tokens and the unrealized gain on
+    /// [StakeReceipt::yi_tokens], given `current_value`, their current value in underlying tokens.
+    pub fn realized_and_unrealized_gain(&self, current_value: u64) -> Option<(i128, i128)> {
+        let withdrawn_basis = self.total_deposited.checked_sub(self.cost_basis)?;
+        let realized = i128::from(self.total_withdrawn).checked_sub(withdrawn_basis.into())?;
+        let unrealized = i128::from(current_value).checked_sub(self.cost_basis.into())?;
+        Some((realized, unrealized))
+    }
+}
+
+/// Waives the stake and unstake fees of a [YiToken] for [FeeExemption::exempt_key], the authority
+/// signing [crate::yi::stake_fee_exempt] or [crate::yi::unstake_fee_exempt].
+///
+/// To exempt a program, exempt a PDA which the program signs with as the authority.
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct FeeExemption {
+    /// The [YiToken].
+    pub yi_token: Pubkey,
+    /// The exempt authority or program.
+    pub exempt_key: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+}
+
+impl FeeExemption {
+    /// Number of bytes in a [FeeExemption].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + 7;
+}
+
+/// Volume brought to a [YiToken] by a referrer via [crate::yi::stake_with_referral].
+#[account(zero_copy)]
+#[derive(Debug, Default)]
+pub struct Referral {
+    /// The [YiToken].
+    pub yi_token: Pubkey,
+    /// The referrer.
+    pub referrer: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Padding.
+    pub _padding: [u8; 7],
+
+    /// Total underlying tokens staked with this referral.
+    pub referred_volume: u64,
+    /// Total underlying tokens paid to the referrer.
+    pub total_rebates: u64,
+    /// Number of stakes made with this referral.
+    pub num_referrals: u64,
+}
+
+impl Referral {
+    /// Number of bytes in a [Referral].
+    pub const SIZE: usize = PUBKEY_BYTES * 2 + 1 + 7 + 8 * 3;
+
+    /// Records a referred stake of `underlying_amount` tokens paying `rebate` to the referrer.
+    pub fn record_referral(&mut self, underlying_amount: u64, rebate: u64) -> Option<()> {
+        self.referred_volume = self.referred_volume.checked_add(underlying_amount)?;
+        self.total_rebates = self.total_rebates.checked_add(rebate)?;
+        self.num_referrals = self.num_referrals.checked_add(1)?;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::integer_arithmetic)]
+mod tests {
+    use std::mem::size_of;
+
+    use super::Strategy;
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_yitoken_size() {
+        assert_eq!(YiToken::SIZE, size_of::<YiToken>());
+    }
+
+    #[test]
+    fn test_calculate_yitokens_for_underlying_init() {
+        let yi_token: YiToken = YiToken::default();
+
+        let amount = yi_token
+            .calculate_yitokens_for_underlying(700_000, 0, 0)
+            .unwrap();
+        assert_eq!(amount, 700_000);
+    }
+
+    #[test]
+    fn test_calculate_yitokens_for_underlying_no_fees() {
+        let yi_token: YiToken = YiToken::default();
+        let amount = yi_token
+            .calculate_yitokens_for_underlying(100_000, 700_000, 700_000)
+            .unwrap();
+        assert_eq!(amount, 100_000);
+    }
+
+    #[test]
+    fn test_calculate_underlying_for_yitokens_init() {
+        let yi_token: YiToken = YiToken::default();
+        let amount = yi_token.calculate_underlying_for_yitokens(700_000, 0, 0);
+        assert_eq!(amount, None);
+    }
+
+    #[test]
+    fn test_calculate_underlying_for_yitokens_no_fees() {
+        let yi_token: YiToken = YiToken::default();
+        let amount = yi_token
+            .calculate_underlying_for_yitokens(100_000, 700_000, 700_000)
+            .unwrap();
+        assert_eq!(amount, 100_000);
+    }
+
+    #[test]
+    fn test_calculate_fees_no_fees() {
+        let yi_token: YiToken = YiToken::default();
+        assert_eq!(
+            yi_token
+                .calculate_stake_fee(100_000, 90_000, 700_000, 700_000)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            yi_token
+                .calculate_unstake_fee(100_000, 90_000, 700_000, 700_000)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_calculate_fees() {
+        let yi_token = YiToken {
+            stake_fee_millibps: 1_000,
+            unstake_fee_millibps: 1_000,
+            ..YiToken::default()
+        };
+
+        let mint_amount = yi_token
+            .calculate_yitokens_for_underlying(100_000, 1_400_000, 700_000)
+            .unwrap();
+        let stake_fee = yi_token
+            .calculate_stake_fee(100_000, mint_amount, 1_400_000, 700_000)
+            .unwrap();
+        assert_eq!(stake_fee, 100_000 - mint_amount * 2);
+
+        let withdraw_amount = yi_token
+            .calculate_underlying_for_yitokens(50_000, 1_400_000, 700_000)
+            .unwrap();
+        let unstake_fee = yi_token
+            .calculate_unstake_fee(50_000, withdraw_amount, 1_400_000, 700_000)
+            .unwrap();
+        assert_eq!(unstake_fee, 100_000 - withdraw_amount);
+
+        // initial stake and full withdrawal are not charged
+        assert_eq!(
+            yi_token
+                .calculate_stake_fee(100_000, 100_000, 0, 0)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            yi_token
+                .calculate_unstake_fee(700_000, 1_400_000, 1_400_000, 700_000)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_record_stats() {
+        let mut yi_token: YiToken = YiToken::default();
+        yi_token.record_stake(100_000, 10).unwrap();
+        yi_token.record_rewards(50_000).unwrap();
+        yi_token.record_unstake(40_000, 5).unwrap();
+
+        assert_eq!(yi_token.total_underlying, 110_000);
+        assert_eq!(yi_token.total_staked, 100_000);
+        assert_eq!(yi_token.total_unstaked, 40_000);
+        assert_eq!(yi_token.total_stake_fees, 10);
+        assert_eq!(yi_token.total_unstake_fees, 5);
+        assert_eq!(yi_token.total_rewards_deposited, 50_000);
+        assert_eq!(yi_token.num_operations, 2);
+
+        // cannot unstake more than the pool holds
+        assert!(yi_token.record_unstake(110_001, 0).is_none());
+    }
+
+    #[test]
+    fn test_calculate_flash_loan_fee() {
+        assert_eq!(YiToken::calculate_flash_loan_fee(0).unwrap(), 0);
+        assert_eq!(YiToken::calculate_flash_loan_fee(1).unwrap(), 1);
+        assert_eq!(YiToken::calculate_flash_loan_fee(1_000_000).unwrap(), 900);
+        assert_eq!(YiToken::calculate_flash_loan_fee(1_000_001).unwrap(), 901);
+        assert_eq!(
+            YiToken::calculate_flash_loan_fee(u64::MAX).unwrap(),
+            16_602_069_666_338_597
+        );
+    }
+
+    #[test]
+    fn test_reward_stream_size() {
+        let stream = RewardStream::default();
+        assert_eq!(RewardStream::SIZE, stream.try_to_vec().unwrap().len());
+        let position = RewardPosition::default();
+        assert_eq!(RewardPosition::SIZE, position.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_reward_stream_accrual() {
+        let mut stream = RewardStream {
+            rewards_per_second: 1_000,
+            last_update_ts: 100,
+            ..RewardStream::default()
+        };
+        let mut alice = RewardPosition::default();
+        let mut bob = RewardPosition::default();
+
+        // nothing accrues without shares
+        stream.accrue(110).unwrap();
+        assert_eq!(stream.reward_per_share, 0);
+
+        alice.update_rewards(&stream).unwrap();
+        alice.deposit(&mut stream, 400).unwrap();
+
+        stream.accrue(120).unwrap();
+        bob.update_rewards(&stream).unwrap();
+        bob.deposit(&mut stream, 100).unwrap();
+
+        stream.accrue(140).unwrap();
+        alice.update_rewards(&stream).unwrap();
+        bob.update_rewards(&stream).unwrap();
+        // alice: 10s alone + 4/5 of 20s, bob: 1/5 of 20s
+        assert_eq!(alice.rewards_earned, 10_000 + 16_000);
+        assert_eq!(bob.rewards_earned, 4_000);
+
+        bob.withdraw(&mut stream, 100).unwrap();
+        assert_eq!(stream.total_shares, 400);
+        assert!(bob.withdraw(&mut stream, 1).is_none());
+
+        // going back in time does nothing
+        stream.accrue(130).unwrap();
+        assert_eq!(stream.last_update_ts, 140);
+    }
+
+    #[test]
+    fn test_swap_route_size() {
+        assert_eq!(SwapRoute::SIZE, size_of::<SwapRoute>());
+    }
+
+    #[test]
+    fn test_strategy_size() {
+        assert_eq!(Strategy::SIZE, size_of::<Strategy>());
+    }
+
+    #[test]
+    fn test_strategy_target() {
+        let strategy = Strategy {
+            max_deployed_millibps: MILLIBPS_PER_WHOLE * 4 / 5,
+            ..Strategy::default()
+        };
+        assert_eq!(strategy.calculate_target(0), Some(0));
+        assert_eq!(strategy.calculate_target(1_000_000), Some(800_000));
+        assert_eq!(strategy.calculate_target(999), Some(799));
+        assert_eq!(strategy.calculate_target(u64::MAX), Some(u64::MAX / 5 * 4));
+    }
+
+    #[test]
+    fn test_realize_strategy_loss() {
+        let mut yi_token = YiToken {
+            total_underlying: 1_000_000,
+            strategy_deployed: 800_000,
+            ..YiToken::default()
+        };
+        // gains are left to be harvested
+        assert_eq!(yi_token.realize_strategy_loss(900_000), Some(0));
+        assert_eq!(yi_token.strategy_deployed, 800_000);
+
+        assert_eq!(yi_token.realize_strategy_loss(600_000), Some(200_000));
+        assert_eq!(yi_token.strategy_deployed, 600_000);
+        assert_eq!(yi_token.total_underlying, 800_000);
+    }
+
+    #[test]
+    fn test_maturity() {
+        let yi_token = YiToken {
+            maturity_ts: 1_000,
+            stake_deadline_ts: 500,
+            ..YiToken::default()
+        };
+        assert!(yi_token.can_stake(500));
+        assert!(!yi_token.can_stake(501));
+        assert!(!yi_token.can_unstake(999));
+        assert!(yi_token.can_unstake(1_000));
+
+        let yi_token = YiToken {
+            early_exit_penalty_millibps: MILLIBPS_PER_WHOLE / 10,
+            ..yi_token
+        };
+        assert!(yi_token.can_unstake(999));
+        assert_eq!(
+            yi_token.calculate_early_exit_penalty(1_000_000, 999),
+            Some(100_000)
+        );
+        assert_eq!(
+            yi_token.calculate_early_exit_penalty(1_000_000, 1_000),
+            Some(0)
+        );
+
+        let yi_token = YiToken::default();
+        assert!(yi_token.can_stake(i64::MAX));
+        assert!(yi_token.can_unstake(0));
+        assert_eq!(yi_token.calculate_early_exit_penalty(1_000_000, 0), Some(0));
+    }
+
+    #[test]
+    fn test_position_size() {
+        assert_eq!(Position::SIZE, size_of::<Position>());
+    }
+
+    #[test]
+    fn test_record_redemption() {
+        let mut position = Position {
+            shares: 900_000,
+            cost_basis: 1_000_000,
+            ..Position::default()
+        };
+        assert_eq!(position.record_redemption(300_000), Some(333_333));
+        assert_eq!(position.shares, 600_000);
+        assert_eq!(position.cost_basis, 666_667);
+
+        // the last redemption takes the remaining basis
+        assert_eq!(position.record_redemption(600_000), Some(666_667));
+        assert_eq!(position.shares, 0);
+        assert_eq!(position.cost_basis, 0);
+
+        assert_eq!(position.record_redemption(1), None);
+    }
+
+    #[test]
+    fn test_stake_receipt_size() {
+        assert_eq!(StakeReceipt::SIZE, size_of::<StakeReceipt>());
+    }
+
+    #[test]
+    fn test_realized_and_unrealized_gain() {
+        let mut receipt = StakeReceipt::default();
+        receipt.record_stake(1_000_000, 1_000_000).unwrap();
+        assert_eq!(
+            receipt.realized_and_unrealized_gain(1_000_000),
+            Some((0, 0))
+        );
+
+        // the rate grows by half
+        assert_eq!(
+            receipt.realized_and_unrealized_gain(1_500_000),
+            Some((0, 500_000))
+        );
+
+        receipt.record_unstake(400_000, 600_000).unwrap();
+        assert_eq!(receipt.yi_tokens, 600_000);
+        assert_eq!(receipt.cost_basis, 600_000);
+        assert_eq!(
+            receipt.realized_and_unrealized_gain(900_000),
+            Some((200_000, 300_000))
+        );
+
+        // a loss on the remaining tokens
+        assert_eq!(
+            receipt.realized_and_unrealized_gain(500_000),
+            Some((200_000, -100_000))
+        );
+
+        receipt.record_unstake(600_000, 900_000).unwrap();
+        assert_eq!(receipt.cost_basis, 0);
+        assert_eq!(receipt.realized_and_unrealized_gain(0), Some((500_000, 0)));
+        assert_eq!(receipt.record_unstake(1, 1), None);
+    }
+
+    #[test]
+    fn test_record_outflow() {
+        let mut yi_token = YiToken::default();
+        assert_eq!(yi_token.record_outflow(u64::MAX, 0), Some(true));
+
+        yi_token.outflow_limit = 1_000;
+        yi_token.outflow_window_seconds = 100;
+        assert_eq!(yi_token.record_outflow(600, 1_050), Some(true));
+        assert_eq!(yi_token.outflow_window_end(), Some(1_150));
+        assert_eq!(yi_token.record_outflow(400, 1_149), Some(true));
+        assert_eq!(yi_token.record_outflow(1, 1_149), Some(false));
+        assert_eq!(yi_token.outflow_in_window, 1_000);
+
+        // a new window starts once the current one ends
+        assert_eq!(yi_token.record_outflow(1, 1_150), Some(true));
+        assert_eq!(yi_token.outflow_window_start, 1_150);
+        assert_eq!(yi_token.outflow_in_window, 1);
+        assert_eq!(yi_token.record_outflow(1_000, 1_200), Some(false));
+    }
+
+    #[test]
+    fn test_has_rate_jumped() {
+        let mut yi_token = YiToken {
+            last_rate: RATE_PRECISION,
+            rate_last_updated_at: 100,
+            // 10%
+            rate_jump_limit_millibps: 1_000_000,
+            ..YiToken::default()
+        };
+        yi_token.record_stake(1_000_000, 0).unwrap();
+        yi_token.update_rate(1_000_000, 100).unwrap();
+        assert_eq!(yi_token.rate_checkpoint, RATE_PRECISION);
+        assert_eq!(yi_token.rate_checkpoint_at, 100);
+        assert_eq!(yi_token.has_rate_jumped(100), Some(false));
+
+        yi_token.record_rewards(100_000).unwrap();
+        yi_token.update_rate(1_000_000, 100).unwrap();
+        assert_eq!(yi_token.has_rate_jumped(100), Some(false));
+        yi_token.record_rewards(1).unwrap();
+        yi_token.update_rate(1_000_000, 100).unwrap();
+        assert_eq!(yi_token.has_rate_jumped(100), Some(true));
+
+        // the rate may keep growing across seconds
+        assert_eq!(yi_token.has_rate_jumped(101), Some(false));
+        let rate = yi_token.last_rate;
+        yi_token.record_rewards(100_000).unwrap();
+        yi_token.update_rate(1_000_000, 101).unwrap();
+        assert_eq!(yi_token.rate_checkpoint, rate);
+        assert_eq!(yi_token.has_rate_jumped(101), Some(false));
+
+        // any fall trips the breaker
+        yi_token.rate_checkpoint = yi_token.last_rate + 1;
+        assert_eq!(yi_token.has_rate_jumped(101), Some(true));
+
+        yi_token.rate_jump_limit_millibps = 0;
+        assert_eq!(yi_token.has_rate_jumped(101), Some(false));
+    }
+
+    #[test]
+    fn test_trip_circuit_breaker() {
+        let mut yi_token = YiToken {
+            last_rate: RATE_PRECISION * 2,
+            rate_checkpoint: RATE_PRECISION,
+            rate_checkpoint_at: 100,
+            // 10%
+            rate_jump_limit_millibps: 1_000_000,
+            ..YiToken::default()
+        };
+        assert_eq!(yi_token.trip_circuit_breaker(101), Some(false));
+        assert_eq!(yi_token.is_paused, 0);
+        assert_eq!(yi_token.trip_circuit_breaker(100), Some(true));
+        assert_eq!(yi_token.is_paused, 1);
+        // already paused
+        assert_eq!(yi_token.trip_circuit_breaker(100), Some(false));
+    }
+
+    #[test]
+    fn test_legacy_yi_token_size() {
+        // the size of every YiToken deployed before internal accounting
+        assert_eq!(YiToken::LEGACY_SIZE, 112);
+    }
+
+    #[test]
+    fn test_fee_exemption_size() {
+        assert_eq!(FeeExemption::SIZE, size_of::<FeeExemption>());
+    }
+
+    #[test]
+    fn test_with_fees_waived() {
+        let yi_token = YiToken {
+            stake_fee_millibps: 9_000_000,
+            unstake_fee_millibps: 9_000_000,
+            ..YiToken::default()
+        };
+        assert_eq!(
+            yi_token.calculate_yitokens_for_underlying(1_000, 1_000, 1_000),
+            Some(900)
+        );
+        let waived = yi_token.with_fees_waived(true);
+        assert_eq!(
+            waived.calculate_yitokens_for_underlying(1_000, 1_000, 1_000),
+            Some(1_000)
+        );
+        assert_eq!(
+            waived.calculate_stake_fee(1_000, 1_000, 1_000, 1_000),
+            Some(0)
+        );
+        assert_eq!(
+            waived.calculate_underlying_for_yitokens(500, 1_000, 1_000),
+            Some(500)
+        );
+        assert_eq!(
+            yi_token
+                .with_fees_waived(false)
+                .calculate_underlying_for_yitokens(500, 1_000, 1_000),
+            Some(450)
+        );
+    }
+
+    #[test]
+    fn test_referral_size() {
+        assert_eq!(Referral::SIZE, size_of::<Referral>());
+    }
+
+    #[test]
+    fn test_calculate_referral_rebate() {
+        let yi_token = YiToken {
+            referral_fee_share_millibps: MILLIBPS_PER_WHOLE / 4,
+            ..YiToken::default()
+        };
+        assert_eq!(yi_token.calculate_referral_rebate(1_000), Some(250));
+        assert_eq!(yi_token.calculate_referral_rebate(0), Some(0));
+        assert_eq!(YiToken::default().calculate_referral_rebate(1_000), Some(0));
+    }
+
+    #[test]
+    fn test_term_size() {
+        assert_eq!(Term::SIZE, size_of::<Term>());
+    }
+
+    #[test]
+    fn test_term_settle() {
+        let mut term = Term {
+            start_rate: RATE_PRECISION * 2,
+            ..Term::default()
+        };
+        // the rate never settles below the start
+        term.settle(RATE_PRECISION);
+        assert_eq!(term.maturity_rate, RATE_PRECISION * 2);
+
+        // later rates do not change a settled term
+        term.settle(RATE_PRECISION * 3);
+        assert_eq!(term.maturity_rate, RATE_PRECISION * 2);
+    }
+
+    #[test]
+    fn test_term_redemptions() {
+        let term = Term {
+            start_rate: RATE_PRECISION * 2,
+            maturity_rate: RATE_PRECISION * 5 / 2,
+            ..Term::default()
+        };
+        let amount = term.calculate_term_tokens_for_yitokens(1_000_000).unwrap();
+        assert_eq!(amount, 2_000_000);
+        assert_eq!(
+            term.calculate_yitokens_for_term_tokens(amount),
+            Some(1_000_000)
+        );
+        assert_eq!(term.calculate_principal_redemption(amount), Some(800_000));
+        assert_eq!(term.calculate_yield_redemption(amount), Some(200_000));
+    }
+
+    proptest! {
+        #[test]
+        fn test_term_redemptions_are_backed(
+            yitoken_amount in 0..u64::MAX / 1_000,
+            start_rate in RATE_PRECISION..RATE_PRECISION * 1_000,
+            growth in 0..RATE_PRECISION * 1_000,
+        ) {
+            let term = Term {
+                start_rate,
+                maturity_rate: start_rate + growth,
+                ..Term::default()
+            };
+            let amount = term.calculate_term_tokens_for_yitokens(yitoken_amount).unwrap();
+            let principal = term.calculate_principal_redemption(amount).unwrap();
+            let yield_ = term.calculate_yield_redemption(amount).unwrap();
+            assert!(principal + yield_ <= yitoken_amount);
+        }
+    }
+
+    #[test]
+    fn test_registrar_size() {
+        assert_eq!(Registrar::SIZE, size_of::<Registrar>());
+    }
+
+    #[test]
+    fn test_voter_size() {
+        assert_eq!(Voter::SIZE, size_of::<Voter>());
+    }
+
+    #[test]
+    fn test_voter_weight_record_size() {
+        let record = VoterWeightRecord {
+            voter_weight_expiry: Some(0),
+            weight_action: Some(VoterWeightAction::CastVote),
+            weight_action_target: Some(Pubkey::default()),
+            ..VoterWeightRecord::default()
+        };
+        assert_eq!(VoterWeightRecord::SIZE, record.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_voter_weight_record_discriminator() {
+        let record = VoterWeightRecord {
+            voter_weight: 100,
+            ..VoterWeightRecord::default()
+        };
+        let mut data = vec![];
+        record.try_serialize(&mut data).unwrap();
+        assert_eq!(&data[..8], b"2ef99b4b");
+        assert_eq!(
+            data.len(),
+            8 + VoterWeightRecord::SIZE - 8 - 1 - PUBKEY_BYTES
+        );
+
+        let decoded = VoterWeightRecord::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(decoded.voter_weight, 100);
+
+        // the discriminator Anchor would derive is not accepted
+        data[..8].copy_from_slice(&[0x2e, 0xf9, 0x9b, 0x4b, 0x99, 0xf8, 0x74, 0x09]);
+        assert!(VoterWeightRecord::try_deserialize(&mut data.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_escrow_size() {
+        assert_eq!(Escrow::SIZE, size_of::<Escrow>());
+    }
+
+    #[test]
+    fn test_voting_power() {
+        let escrow = Escrow {
+            amount: 1_000_000,
+            unlock_at: MAX_ESCROW_LOCK_SECONDS,
+            ..Escrow::default()
+        };
+        assert_eq!(escrow.voting_power(0), Some(1_000_000));
+        assert_eq!(
+            escrow.voting_power(MAX_ESCROW_LOCK_SECONDS / 4),
+            Some(750_000)
+        );
+        assert_eq!(escrow.voting_power(MAX_ESCROW_LOCK_SECONDS), Some(0));
+        assert_eq!(escrow.voting_power(MAX_ESCROW_LOCK_SECONDS + 1), Some(0));
+        // locks beyond the maximum are capped
+        assert_eq!(
+            escrow.voting_power(-MAX_ESCROW_LOCK_SECONDS),
+            Some(1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_harvest_adapter_size() {
+        assert_eq!(HarvestAdapter::SIZE, size_of::<HarvestAdapter>());
+    }
+
+    #[test]
+    fn test_emission_schedule_size() {
+        assert_eq!(EmissionSchedule::SIZE, size_of::<EmissionSchedule>());
+    }
+
+    #[test]
+    fn test_calculate_emission() {
+        let schedule = EmissionSchedule {
+            emissions_per_second: 100,
+            end_ts: 1_000,
+            last_crank_ts: 500,
+            bounty_millibps: 10 * 1_000,
+            ..EmissionSchedule::default()
+        };
+        assert_eq!(schedule.calculate_emission(400, 1_000_000), Some((0, 500)));
+        assert_eq!(
+            schedule.calculate_emission(510, 1_000_000),
+            Some((1_000, 510))
+        );
+        // limited by the end time
+        assert_eq!(
+            schedule.calculate_emission(2_000, 1_000_000),
+            Some((50_000, 1_000))
+        );
+        // limited by the reserve
+        assert_eq!(schedule.calculate_emission(2_000, 123), Some((123, 1_000)));
+
+        assert_eq!(schedule.calculate_bounty(50_000), Some(50));
+        assert_eq!(schedule.calculate_bounty(999), Some(0));
+    }
+
+    #[test]
+    fn test_rate_provider_size() {
+        assert_eq!(RateProvider::SIZE, size_of::<RateProvider>());
+    }
+
+    #[test]
+    fn test_rate_oracle_size() {
+        assert_eq!(RateOracle::SIZE, size_of::<RateOracle>());
+    }
+
+    #[test]
+    fn test_update_rate() {
+        let mut yi_token = YiToken {
+            last_rate: RATE_PRECISION,
+            rate_last_updated_at: 100,
+            ..YiToken::default()
+        };
+        yi_token.record_stake(1_000_000, 0).unwrap();
+        yi_token.update_rate(1_000_000, 110).unwrap();
+        assert_eq!(yi_token.rate_cumulative, (RATE_PRECISION * 10).into());
+        assert_eq!(yi_token.last_rate, RATE_PRECISION);
+
+        // rewards double the rate
+        yi_token.record_rewards(1_000_000).unwrap();
+        yi_token.update_rate(1_000_000, 120).unwrap();
+        assert_eq!(yi_token.rate_cumulative, (RATE_PRECISION * 20).into());
+        assert_eq!(yi_token.last_rate, RATE_PRECISION * 2);
+
+        assert_eq!(
+            yi_token.current_rate_cumulative(130).unwrap(),
+            (RATE_PRECISION * 40).into()
+        );
+        // cannot go back in time
+        assert_eq!(yi_token.current_rate_cumulative(119), None);
+    }
+
+    #[test]
+    fn test_twap_beyond_u64() {
+        let start = RateObservation {
+            timestamp: 0,
+            rate_cumulative: u64::MAX as u128,
+            ..RateObservation::default()
+        };
+        let end = RateObservation {
+            timestamp: 10,
+            rate_cumulative: u64::MAX as u128 + (RATE_PRECISION * 20) as u128,
+            ..RateObservation::default()
+        };
+        assert_eq!(start.twap(&end).unwrap(), RATE_PRECISION * 2);
+        assert_eq!(end.twap(&start), None);
+        assert_eq!(end.twap(&end), None);
+    }
+
+    #[test]
+    fn test_rate_oracle_ring_buffer() {
+        let mut oracle = RateOracle {
+            yi_token: Pubkey::default(),
+            bump: 0,
+            _padding: [0; 15],
+            latest_index: 0,
+            num_observations: 0,
+            observations: [RateObservation::default(); RATE_ORACLE_NUM_OBSERVATIONS],
+        };
+        assert_eq!(oracle.latest(), None);
+        assert_eq!(oracle.oldest(), None);
+
+        let num_records = RATE_ORACLE_NUM_OBSERVATIONS + 10;
+        for i in 0..num_records {
+            oracle
+                .record(RateObservation {
+                    timestamp: i as i64,
+                    rate_cumulative: i as u128 * RATE_PRECISION as u128,
+                    ..RateObservation::default()
+                })
+                .unwrap();
+        }
+        assert_eq!(oracle.num_observations, RATE_ORACLE_NUM_OBSERVATIONS as u64);
+        assert_eq!(oracle.latest().unwrap().timestamp, num_records as i64 - 1);
+        assert_eq!(oracle.oldest().unwrap().timestamp, 10);
+        assert_eq!(oracle.observation_at_or_before(30).unwrap().timestamp, 30);
+        assert_eq!(oracle.observation_at_or_before(9), None);
+        assert_eq!(
+            oracle
+                .oldest()
+                .unwrap()
+                .twap(oracle.latest().unwrap())
+                .unwrap(),
+            RATE_PRECISION
+        );
     }
 
     fn perform_test_cannot_increase_no_fees(
@@ -1,4 +1,5 @@
 import { buildCoderMap } from "@saberhq/anchor-contrib";
+import { u64 } from "@saberhq/token-utils";
 import { PublicKey } from "@solana/web3.js";
 
 import type { YiProgram, YiTypes } from "./programs";
@@ -18,6 +19,16 @@ export const YI_ADDRESSES = {
   Yi: new PublicKey("YiiTopEnX2vyoWdXuG45ovDFYZars4XZ4w6td6RVTFm"),
 };
 
+/**
+ * Amount passed to `stake` or `unstake` to use the full balance of the source account.
+ */
+export const ALL_TOKENS = new u64("18446744073709551615");
+
+/**
+ * Discriminator of a VoterWeightRecord, as defined by the SPL Governance addin API.
+ */
+export const VOTER_WEIGHT_RECORD_DISCRIMINATOR = Buffer.from("2ef99b4b");
+
 /**
  * Program IDLs.
  */
@@ -1,3 +1,4 @@
+import type { BN } from "@project-serum/anchor";
 import { utils } from "@project-serum/anchor";
 import { PublicKey } from "@solana/web3.js";
 
@@ -14,3 +15,304 @@ export const findYiTokenAddress = async (
     YI_ADDRESSES.Yi
   );
 };
+
+/**
+ * Finds the address of a RateOracle.
+ */
+export const findRateOracleAddress = async (
+  yiToken: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("RateOracle"), yiToken.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a RateProvider.
+ */
+export const findRateProviderAddress = async (
+  yiToken: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("RateProvider"), yiToken.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a RewardStream.
+ */
+export const findRewardStreamAddress = async (
+  yiToken: PublicKey,
+  rewardMint: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("RewardStream"),
+      yiToken.toBuffer(),
+      rewardMint.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a RewardPosition.
+ */
+export const findRewardPositionAddress = async (
+  rewardStream: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("RewardPosition"),
+      rewardStream.toBuffer(),
+      owner.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of an EmissionSchedule.
+ */
+export const findEmissionScheduleAddress = async (
+  yiToken: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("EmissionSchedule"), yiToken.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a HarvestAdapter.
+ */
+export const findHarvestAdapterAddress = async (
+  yiToken: PublicKey,
+  adapterProgram: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("HarvestAdapter"),
+      yiToken.toBuffer(),
+      adapterProgram.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of the Strategy of a YiToken.
+ */
+export const findStrategyAddress = async (
+  yiToken: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("Strategy"), yiToken.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the authority of a strategy program which must own the strategy tokens of a YiToken.
+ */
+export const findStrategyAuthorityAddress = async (
+  yiToken: PublicKey,
+  strategyProgram: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("StrategyAuthority"), yiToken.toBuffer()],
+    strategyProgram
+  );
+};
+
+/**
+ * Finds the address of a SwapRoute.
+ */
+export const findSwapRouteAddress = async (
+  yiToken: PublicKey,
+  inputMint: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SwapRoute"),
+      yiToken.toBuffer(),
+      inputMint.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of an Escrow.
+ */
+export const findEscrowAddress = async (
+  yiToken: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("Escrow"), yiToken.toBuffer(), owner.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a Term.
+ */
+export const findTermAddress = async (
+  yiToken: PublicKey,
+  maturityTs: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("Term"),
+      yiToken.toBuffer(),
+      maturityTs.toArrayLike(Buffer, "le", 8),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a Registrar.
+ */
+export const findRegistrarAddress = async (
+  realm: PublicKey,
+  governingTokenMint: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("Registrar"),
+      realm.toBuffer(),
+      governingTokenMint.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a Voter.
+ */
+export const findVoterAddress = async (
+  registrar: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("Voter"), registrar.toBuffer(), owner.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of the VoterWeightRecord of a Voter.
+ */
+export const findVoterWeightRecordAddress = async (
+  registrar: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("VoterWeightRecord"),
+      registrar.toBuffer(),
+      owner.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of the SPL Governance token owner record of an owner.
+ */
+export const findTokenOwnerRecordAddress = async (
+  governanceProgram: PublicKey,
+  realm: PublicKey,
+  governingTokenMint: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("governance"),
+      realm.toBuffer(),
+      governingTokenMint.toBuffer(),
+      owner.toBuffer(),
+    ],
+    governanceProgram
+  );
+};
+
+/**
+ * Finds the address of a Position.
+ */
+export const findPositionAddress = async (
+  positionMint: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("Position"), positionMint.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a StakeReceipt.
+ */
+export const findStakeReceiptAddress = async (
+  yiToken: PublicKey,
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("StakeReceipt"),
+      yiToken.toBuffer(),
+      owner.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a Referral.
+ */
+export const findReferralAddress = async (
+  yiToken: PublicKey,
+  referrer: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("Referral"),
+      yiToken.toBuffer(),
+      referrer.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of a FeeExemption.
+ */
+export const findFeeExemptionAddress = async (
+  yiToken: PublicKey,
+  exemptKey: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("FeeExemption"),
+      yiToken.toBuffer(),
+      exemptKey.toBuffer(),
+    ],
+    YI_ADDRESSES.Yi
+  );
+};
+
+/**
+ * Finds the address of the temporary wrapped SOL account used to stake or unstake SOL.
+ */
+export const findWrappedSolAddress = async (
+  owner: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("WrappedSol"), owner.toBuffer()],
+    YI_ADDRESSES.Yi
+  );
+};
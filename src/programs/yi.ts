@@ -8,11 +8,48 @@ export type YiTypes = AnchorTypes<
   YiIDL,
   {
     yiToken: YiTokenData;
+    rateOracle: RateOracleData;
+    rateProvider: RateProviderData;
+    rewardStream: RewardStreamData;
+    rewardPosition: RewardPositionData;
+    emissionSchedule: EmissionScheduleData;
+    harvestAdapter: HarvestAdapterData;
+    strategy: StrategyData;
+    swapRoute: SwapRouteData;
+    escrow: EscrowData;
+    term: TermData;
+    registrar: RegistrarData;
+    voter: VoterData;
+    position: PositionData;
+    stakeReceipt: StakeReceiptData;
+    referral: ReferralData;
+    feeExemption: FeeExemptionData;
   }
 >;
 
 type Accounts = YiTypes["Accounts"];
 
 export type YiTokenData = Accounts["YiToken"];
+export type RateOracleData = Accounts["RateOracle"];
+export type RateProviderData = Accounts["RateProvider"];
+export type RewardStreamData = Accounts["RewardStream"];
+export type RewardPositionData = Accounts["RewardPosition"];
+export type EmissionScheduleData = Accounts["EmissionSchedule"];
+export type HarvestAdapterData = Accounts["HarvestAdapter"];
+export type StrategyData = Accounts["Strategy"];
+export type SwapRouteData = Accounts["SwapRoute"];
+export type EscrowData = Accounts["Escrow"];
+export type TermData = Accounts["Term"];
+export type RegistrarData = Accounts["Registrar"];
+export type VoterData = Accounts["Voter"];
+/**
+ * VoterWeightRecords use the SPL Governance discriminator, so they are
+ * decoded as a defined type rather than an Anchor account.
+ */
+export type VoterWeightRecordData = YiTypes["Defined"]["VoterWeightRecord"];
+export type PositionData = Accounts["Position"];
+export type StakeReceiptData = Accounts["StakeReceipt"];
+export type ReferralData = Accounts["Referral"];
+export type FeeExemptionData = Accounts["FeeExemption"];
 
 export type YiProgram = YiTypes["Program"];
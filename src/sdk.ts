@@ -1,3 +1,4 @@
+import { BN } from "@project-serum/anchor";
 import { newProgramMap } from "@saberhq/anchor-contrib";
 import type {
   AugmentedProvider,
@@ -7,17 +8,82 @@ import type {
 import { SolanaAugmentedProvider } from "@saberhq/solana-contrib";
 import type { Token, TokenAmount, u64 } from "@saberhq/token-utils";
 import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
   createInitMintInstructions,
+  getATAAddress,
   getOrCreateATA,
   getOrCreateATAs,
   TOKEN_PROGRAM_ID,
 } from "@saberhq/token-utils";
-import type { PublicKey, Signer } from "@solana/web3.js";
-import { Keypair, SystemProgram } from "@solana/web3.js";
+import type {
+  AccountMeta,
+  PublicKey,
+  Signer,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  Keypair,
+  PublicKey as SolanaPublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+} from "@solana/web3.js";
 
 import type { YiPrograms } from ".";
-import { findYiTokenAddress } from ".";
-import { YI_ADDRESSES, YI_IDLS } from "./constants";
+import {
+  findEmissionScheduleAddress,
+  findEscrowAddress,
+  findFeeExemptionAddress,
+  findPositionAddress,
+  findRegistrarAddress,
+  findStakeReceiptAddress,
+  findTermAddress,
+  findTokenOwnerRecordAddress,
+  findVoterAddress,
+  findVoterWeightRecordAddress,
+  findHarvestAdapterAddress,
+  findRateOracleAddress,
+  findRateProviderAddress,
+  findReferralAddress,
+  findRewardPositionAddress,
+  findRewardStreamAddress,
+  findStrategyAddress,
+  findSwapRouteAddress,
+  findWrappedSolAddress,
+  findYiTokenAddress,
+} from ".";
+import {
+  VOTER_WEIGHT_RECORD_DISCRIMINATOR,
+  YI_ADDRESSES,
+  YI_IDLS,
+} from "./constants";
+import type { VoterWeightRecordData } from "./programs";
+
+/**
+ * Mint of wrapped SOL.
+ */
+const NATIVE_MINT = new SolanaPublicKey(
+  "So11111111111111111111111111111111111111112"
+);
+
+/**
+ * Remaining accounts for refreshing a rate provider.
+ */
+const rateProviderAccountMetas = (rateProvider?: PublicKey): AccountMeta[] =>
+  rateProvider
+    ? [{ pubkey: rateProvider, isSigner: false, isWritable: true }]
+    : [];
+
+/**
+ * Remaining accounts for a batch stake or unstake.
+ */
+const batchAccountMetas = (
+  entries: readonly { source: PublicKey; destination: PublicKey }[]
+): AccountMeta[] =>
+  entries.flatMap(({ source, destination }) => [
+    { pubkey: source, isSigner: false, isWritable: true },
+    { pubkey: destination, isSigner: false, isWritable: true },
+  ]);
 
 /**
  * Yi SDK.
@@ -60,6 +126,8 @@ export class YiSDK {
     unstakeFeeMillibps = 0,
     mintKP = Keypair.generate(),
     payer = this.provider.wallet.publicKey,
+    admin,
+    maturity,
   }: {
     underlyingToken: Token;
     stakeFeeMillibps?: number;
@@ -69,6 +137,24 @@ export class YiSDK {
      */
     mintKP?: Signer;
     payer?: PublicKey;
+    /**
+     * Admin of the YiToken, if any.
+     */
+    admin?: PublicKey;
+    /**
+     * Maturity of the YiToken, if it is a fixed-maturity pool.
+     */
+    maturity?: {
+      maturityTs: BN;
+      /**
+       * Time after which staking closes, if any.
+       */
+      stakeDeadlineTs?: BN;
+      /**
+       * Penalty for unstaking before maturity. If zero, early unstakes are rejected.
+       */
+      earlyExitPenaltyMillibps?: number;
+    };
   }): Promise<{
     tx: TransactionEnvelope;
     mint: PublicKey;
@@ -87,26 +173,52 @@ export class YiSDK {
       mintAuthority: yiToken,
       freezeAuthority: yiToken,
     });
+    const createAccounts = {
+      mint: mintKP.publicKey,
+      yiToken,
+      underlyingTokenMint: underlyingToken.mintAccount,
+      underlyingTokens: underlyingTokens.address,
+      payer,
+      systemProgram: SystemProgram.programId,
+    };
     return {
       yiToken,
       mint: mintKP.publicKey,
       tx: initMintTX.combine(
         this.provider.newTX([
           underlyingTokens.instruction,
-          this.programs.Yi.instruction.createYiTokenWithFees(
-            stakeFeeMillibps,
-            unstakeFeeMillibps,
-            {
-              accounts: {
-                mint: mintKP.publicKey,
-                yiToken,
-                underlyingTokenMint: underlyingToken.mintAccount,
-                underlyingTokens: underlyingTokens.address,
-                payer,
-                systemProgram: SystemProgram.programId,
-              },
-            }
-          ),
+          maturity
+            ? this.programs.Yi.instruction.createYiTokenWithMaturity(
+                stakeFeeMillibps,
+                unstakeFeeMillibps,
+                maturity.maturityTs,
+                maturity.stakeDeadlineTs ?? new BN(0),
+                maturity.earlyExitPenaltyMillibps ?? 0,
+                {
+                  accounts: {
+                    create: createAccounts,
+                    admin: admin ?? SolanaPublicKey.default,
+                  },
+                }
+              )
+            : admin
+            ? this.programs.Yi.instruction.createYiTokenWithAdmin(
+                stakeFeeMillibps,
+                unstakeFeeMillibps,
+                {
+                  accounts: {
+                    create: createAccounts,
+                    admin,
+                  },
+                }
+              )
+            : this.programs.Yi.instruction.createYiTokenWithFees(
+                stakeFeeMillibps,
+                unstakeFeeMillibps,
+                {
+                  accounts: createAccounts,
+                }
+              ),
         ])
       ),
     };
@@ -120,6 +232,8 @@ export class YiSDK {
     yiTokenMint,
     amount,
     authority = this.provider.wallet.publicKey,
+    rateProvider,
+    minAmountOut,
   }: {
     /**
      * Mint of the Yi token.
@@ -133,6 +247,14 @@ export class YiSDK {
      * Authority staking tokens.
      */
     authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+    /**
+     * Minimum number of Yi tokens to mint, if any.
+     */
+    minAmountOut?: u64;
   }): Promise<TransactionEnvelope> {
     const [yiToken] = await findYiTokenAddress(yiTokenMint);
     const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
@@ -144,19 +266,27 @@ export class YiSDK {
       },
       owner: authority,
     });
+    const ctx = {
+      accounts: {
+        yiToken,
+        yiMint: yiTokenMint,
+        sourceTokens: authorityATAs.accounts.underlying,
+        sourceAuthority: authority,
+        yiUnderlyingTokens: yiTokenData.underlyingTokens,
+        destinationYiTokens: authorityATAs.accounts.yi,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+      remainingAccounts: rateProviderAccountMetas(rateProvider),
+    };
     return this.provider.newTX([
       authorityATAs.createAccountInstructions.yi,
-      this.programs.Yi.instruction.stake(amount, {
-        accounts: {
-          yiToken,
-          yiMint: yiTokenMint,
-          sourceTokens: authorityATAs.accounts.underlying,
-          sourceAuthority: authority,
-          yiUnderlyingTokens: yiTokenData.underlyingTokens,
-          destinationYiTokens: authorityATAs.accounts.yi,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        },
-      }),
+      minAmountOut
+        ? this.programs.Yi.instruction.stakeWithMinOut(
+            amount,
+            minAmountOut,
+            ctx
+          )
+        : this.programs.Yi.instruction.stake(amount, ctx),
     ]);
   }
 
@@ -167,6 +297,7 @@ export class YiSDK {
   async unstake({
     amount,
     authority = this.provider.wallet.publicKey,
+    rateProvider,
   }: {
     /**
      * Yi Token Amount
@@ -176,6 +307,10 @@ export class YiSDK {
      * Authority unstaking tokens.
      */
     authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
   }): Promise<TransactionEnvelope> {
     const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
     const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
@@ -199,7 +334,2962 @@ export class YiSDK {
           destinationUnderlyingTokens: authorityATAs.accounts.underlying,
           tokenProgram: TOKEN_PROGRAM_ID,
         },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Moves staked tokens from one Yi token to another with the same underlying token.
+   * @returns
+   */
+  async migrateStake({
+    amount,
+    toYiTokenMint,
+    minAmountOut,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Amount of Yi tokens to migrate.
+     */
+    amount: TokenAmount;
+    /**
+     * Mint of the Yi token to migrate into.
+     */
+    toYiTokenMint: PublicKey;
+    /**
+     * Minimum amount of Yi tokens to receive.
+     */
+    minAmountOut: u64;
+    /**
+     * Authority migrating tokens.
+     */
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [fromYiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const fromYiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      fromYiToken
+    );
+    const [toYiToken] = await findYiTokenAddress(toYiTokenMint);
+    const toYiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      toYiToken
+    );
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: fromYiTokenData.underlyingTokenMint,
+        from: amount.token.mintAccount,
+        to: toYiTokenMint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.migrateStake(amount.toU64(), minAmountOut, {
+        accounts: {
+          unstake: {
+            yiToken: fromYiToken,
+            yiMint: amount.token.mintAccount,
+            sourceYiTokens: authorityATAs.accounts.from,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: fromYiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          stake: {
+            yiToken: toYiToken,
+            yiMint: toYiTokenMint,
+            sourceTokens: authorityATAs.accounts.underlying,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: toYiTokenData.underlyingTokens,
+            destinationYiTokens: authorityATAs.accounts.to,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Stakes underlying tokens from many source accounts owned by the same authority.
+   * @returns
+   */
+  async batchStake({
+    yiTokenMint,
+    entries,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amounts of underlying tokens to stake from each source into each destination.
+     */
+    entries: readonly {
+      amount: u64;
+      source: PublicKey;
+      destination: PublicKey;
+    }[];
+    /**
+     * Owner of every source account.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.batchStake(
+        entries.map((entry) => entry.amount),
+        {
+          accounts: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          remainingAccounts: [
+            ...batchAccountMetas(entries),
+            ...rateProviderAccountMetas(rateProvider),
+          ],
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens from many source accounts owned by the same authority.
+   * @returns
+   */
+  async batchUnstake({
+    yiTokenMint,
+    entries,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amounts of Yi tokens to unstake from each source into each destination.
+     */
+    entries: readonly {
+      amount: u64;
+      source: PublicKey;
+      destination: PublicKey;
+    }[];
+    /**
+     * Owner of every source account.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.batchUnstake(
+        entries.map((entry) => entry.amount),
+        {
+          accounts: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          remainingAccounts: [
+            ...batchAccountMetas(entries),
+            ...rateProviderAccountMetas(rateProvider),
+          ],
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Unstakes all of the authority's Yi tokens, then closes the emptied Yi token account.
+   * @returns
+   */
+  async unstakeAllAndClose({
+    yiTokenMint,
+    authority = this.provider.wallet.publicKey,
+    rentRecipient = authority,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Receives the rent of the closed Yi token account.
+     */
+    rentRecipient?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.underlyingTokenMint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.unstakeAllAndClose({
+        accounts: {
+          unstake: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceYiTokens: await getATAAddress({
+              mint: yiTokenMint,
+              owner: authority,
+            }),
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: destination.address,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          rentRecipient,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Stakes underlying tokens, creating the authority's Yi token account in the program if needed.
+   * @returns
+   */
+  async stakeToATA({
+    yiTokenMint,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    /**
+     * Authority staking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Payer of the Yi token account, if it is created.
+     */
+    payer?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.stakeToAta(amount, {
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          sourceTokens: await getATAAddress({
+            mint: yiTokenData.underlyingTokenMint,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          destinationYiTokens: await getATAAddress({
+            mint: yiTokenMint,
+            owner: authority,
+          }),
+          payer,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens, creating the authority's underlying token account in the program if needed.
+   * @returns
+   */
+  async unstakeToATA({
+    amount,
+    authority = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Yi Token Amount
+     */
+    amount: TokenAmount;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Payer of the underlying token account, if it is created.
+     */
+    payer?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.unstakeToAta(amount.toU64(), {
+        accounts: {
+          yiToken,
+          yiMint: amount.token.mintAccount,
+          sourceYiTokens: await getATAAddress({
+            mint: amount.token.mintAccount,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          underlyingMint: yiTokenData.underlyingTokenMint,
+          destinationUnderlyingTokens: await getATAAddress({
+            mint: yiTokenData.underlyingTokenMint,
+            owner: authority,
+          }),
+          payer,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
       }),
     ]);
   }
+
+  /**
+   * Stakes lamports into a wrapped SOL Yi token.
+   * @returns
+   */
+  async stakeSol({
+    yiTokenMint,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of lamports to stake.
+     */
+    amount: u64;
+    /**
+     * Authority staking lamports.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [wrappedSol] = await findWrappedSolAddress(authority);
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenMint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.stakeSol(amount, {
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          wrappedSol,
+          nativeMint: NATIVE_MINT,
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          destinationYiTokens: destination.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens of a wrapped SOL Yi token for lamports.
+   * @returns
+   */
+  async unstakeSol({
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Yi Token Amount
+     */
+    amount: TokenAmount;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [wrappedSol] = await findWrappedSolAddress(authority);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.unstakeSol(amount.toU64(), {
+        accounts: {
+          yiToken,
+          yiMint: amount.token.mintAccount,
+          sourceYiTokens: await getATAAddress({
+            mint: amount.token.mintAccount,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          wrappedSol,
+          nativeMint: NATIVE_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Deposits underlying tokens into the pool as rewards for Yi token holders.
+   * @returns
+   */
+  async depositRewards({
+    yiTokenMint,
+    amount,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to deposit.
+     */
+    amount: u64;
+    /**
+     * Authority depositing tokens.
+     */
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const sourceTokens = await getATAAddress({
+      mint: yiTokenData.underlyingTokenMint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      this.programs.Yi.instruction.depositRewards(amount, {
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          sourceTokens,
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Recognizes underlying tokens sent directly to the pool as rewards.
+   * @returns
+   */
+  async sync({
+    yiTokenMint,
+    admin = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    admin?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.sync({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          admin,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Resizes a Yi token created before internal accounting to the current layout.
+   * @returns
+   */
+  async migrateYiToken({
+    yiTokenMint,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    payer?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    // legacy accounts are too small to decode, so read the vault from the raw data
+    const yiTokenInfo = await this.provider.connection.getAccountInfo(yiToken);
+    if (!yiTokenInfo) {
+      throw new Error(`YiToken ${yiToken.toString()} not found`);
+    }
+    // discriminator, mint, bump, padding and underlying mint
+    const underlyingTokensOffset = 8 + 32 + 1 + 7 + 32;
+    const yiUnderlyingTokens = new SolanaPublicKey(
+      yiTokenInfo.data.slice(
+        underlyingTokensOffset,
+        underlyingTokensOffset + 32
+      )
+    );
+    return this.provider.newTX([
+      this.programs.Yi.instruction.migrateYiToken({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          yiUnderlyingTokens,
+          payer,
+          systemProgram: SystemProgram.programId,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a rate oracle for a Yi token.
+   * @returns
+   */
+  async createRateOracle({
+    yiTokenMint,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    rateOracle: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [rateOracle] = await findRateOracleAddress(yiToken);
+    return {
+      rateOracle,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createRateOracle({
+          accounts: {
+            yiToken,
+            rateOracle,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Records an exchange rate observation into the rate oracle of a Yi token.
+   * @returns
+   */
+  async recordRateObservation({
+    yiTokenMint,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [rateOracle] = await findRateOracleAddress(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.recordRateObservation({
+        accounts: {
+          yiToken,
+          rateOracle,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a rate provider for a Yi token.
+   * @returns
+   */
+  async createRateProvider({
+    yiTokenMint,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    rateProvider: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [rateProvider] = await findRateProviderAddress(yiToken);
+    return {
+      rateProvider,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createRateProvider({
+          accounts: {
+            yiToken,
+            yiMint: yiTokenMint,
+            rateProvider,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Refreshes the rate stored in the rate provider of a Yi token.
+   * @returns
+   */
+  async refreshRate({
+    yiTokenMint,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [rateProvider] = await findRateProviderAddress(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.refreshRate({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          rateProvider,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Builds the instructions to borrow underlying tokens from a Yi token and repay them
+   * within the same transaction.
+   * @returns
+   */
+  async flashLoan({
+    yiTokenMint,
+    amount,
+    instructions = [],
+    authority = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to borrow.
+     */
+    amount: u64;
+    /**
+     * Instructions to execute with the borrowed tokens.
+     */
+    instructions?: TransactionInstruction[];
+    /**
+     * Authority receiving and repaying the loan. Their account must hold the fee.
+     */
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityTokens = await getATAAddress({
+      mint: yiTokenData.underlyingTokenMint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      this.programs.Yi.instruction.flashLoan(amount, {
+        accounts: {
+          yiToken,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          destinationTokens: authorityTokens,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+      ...instructions,
+      this.programs.Yi.instruction.flashRepay({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          sourceTokens: authorityTokens,
+          sourceAuthority: authority,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a reward stream of a secondary reward token for Yi token holders.
+   * @returns
+   */
+  async createRewardStream({
+    yiTokenMint,
+    rewardMint,
+    authority = this.provider.wallet.publicKey,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Mint of the reward token.
+     */
+    rewardMint: PublicKey;
+    /**
+     * Authority which may set the reward rate.
+     */
+    authority?: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    rewardStream: PublicKey;
+    rewardTokens: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [rewardStream] = await findRewardStreamAddress(yiToken, rewardMint);
+    const rewardTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: rewardMint,
+      owner: rewardStream,
+    });
+    return {
+      rewardStream,
+      rewardTokens: rewardTokens.address,
+      tx: this.provider.newTX([
+        rewardTokens.instruction,
+        this.programs.Yi.instruction.createRewardStream({
+          accounts: {
+            yiToken,
+            rewardMint,
+            stream: rewardStream,
+            rewardTokens: rewardTokens.address,
+            authority,
+            admin,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Sets the number of reward tokens a reward stream distributes per second.
+   * @returns
+   */
+  setRewardRate({
+    rewardStream,
+    rewardsPerSecond,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    rewardStream: PublicKey;
+    rewardsPerSecond: u64;
+    authority?: PublicKey;
+  }): TransactionEnvelope {
+    return this.provider.newTX([
+      this.programs.Yi.instruction.setRewardRate(rewardsPerSecond, {
+        accounts: {
+          stream: rewardStream,
+          authority,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a reward position for earning from a reward stream.
+   * @returns
+   */
+  async createRewardPosition({
+    rewardStream,
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    rewardStream: PublicKey;
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    rewardPosition: PublicKey;
+  }> {
+    const streamData = await this.programs.Yi.account.rewardStream.fetch(
+      rewardStream
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      streamData.yiToken
+    );
+    const [rewardPosition] = await findRewardPositionAddress(
+      rewardStream,
+      owner
+    );
+    const positionYiTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.mint,
+      owner: rewardPosition,
+    });
+    return {
+      rewardPosition,
+      tx: this.provider.newTX([
+        positionYiTokens.instruction,
+        this.programs.Yi.instruction.createRewardPosition({
+          accounts: {
+            yiToken: streamData.yiToken,
+            stream: rewardStream,
+            position: rewardPosition,
+            positionYiTokens: positionYiTokens.address,
+            owner,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Stakes underlying tokens, depositing the Yi tokens into a reward position.
+   * @returns
+   */
+  async stakeWithRewards({
+    rewardStream,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    rewardStream: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    /**
+     * Owner of the reward position.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const streamData = await this.programs.Yi.account.rewardStream.fetch(
+      rewardStream
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      streamData.yiToken
+    );
+    const [rewardPosition] = await findRewardPositionAddress(
+      rewardStream,
+      authority
+    );
+    const positionData = await this.programs.Yi.account.rewardPosition.fetch(
+      rewardPosition
+    );
+    const sourceTokens = await getATAAddress({
+      mint: yiTokenData.underlyingTokenMint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      this.programs.Yi.instruction.stakeWithRewards(amount, {
+        accounts: {
+          stake: {
+            yiToken: streamData.yiToken,
+            yiMint: yiTokenData.mint,
+            sourceTokens,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationYiTokens: positionData.yiTokens,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          rewardPosition: {
+            stream: rewardStream,
+            position: rewardPosition,
+            owner: authority,
+          },
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens held in a reward position.
+   * @returns
+   */
+  async unstakeWithRewards({
+    rewardStream,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    rewardStream: PublicKey;
+    /**
+     * Amount of Yi tokens to unstake.
+     */
+    amount: u64;
+    /**
+     * Owner of the reward position.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const streamData = await this.programs.Yi.account.rewardStream.fetch(
+      rewardStream
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      streamData.yiToken
+    );
+    const [rewardPosition] = await findRewardPositionAddress(
+      rewardStream,
+      authority
+    );
+    const positionData = await this.programs.Yi.account.rewardPosition.fetch(
+      rewardPosition
+    );
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.unstakeWithRewards(amount, {
+        accounts: {
+          unstake: {
+            yiToken: streamData.yiToken,
+            yiMint: yiTokenData.mint,
+            sourceYiTokens: authorityATAs.accounts.yi,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          rewardPosition: {
+            stream: rewardStream,
+            position: rewardPosition,
+            owner: authority,
+          },
+          positionYiTokens: positionData.yiTokens,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Claims the rewards earned by a reward position.
+   * @returns
+   */
+  async claimRewards({
+    rewardStream,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    rewardStream: PublicKey;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const streamData = await this.programs.Yi.account.rewardStream.fetch(
+      rewardStream
+    );
+    const [rewardPosition] = await findRewardPositionAddress(
+      rewardStream,
+      owner
+    );
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: streamData.rewardMint,
+      owner,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.claimRewards({
+        accounts: {
+          rewardPosition: {
+            stream: rewardStream,
+            position: rewardPosition,
+            owner,
+          },
+          rewardTokens: streamData.rewardTokens,
+          destinationRewardTokens: destination.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates an emission schedule which emits underlying tokens into a Yi token.
+   * @returns
+   */
+  async createEmissionSchedule({
+    yiTokenMint,
+    emissionsPerSecond,
+    endTs,
+    bountyMillibps = 0,
+    authority = this.provider.wallet.publicKey,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Underlying tokens emitted per second.
+     */
+    emissionsPerSecond: u64;
+    /**
+     * Unix timestamp after which nothing is emitted.
+     */
+    endTs: BN;
+    /**
+     * Share of each crank paid to the cranker, in thousands of bps.
+     */
+    bountyMillibps?: number;
+    /**
+     * Authority which may change the schedule.
+     */
+    authority?: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    emissionSchedule: PublicKey;
+    reserveTokens: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [emissionSchedule] = await findEmissionScheduleAddress(yiToken);
+    const reserveTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.underlyingTokenMint,
+      owner: emissionSchedule,
+    });
+    return {
+      emissionSchedule,
+      reserveTokens: reserveTokens.address,
+      tx: this.provider.newTX([
+        reserveTokens.instruction,
+        this.programs.Yi.instruction.createEmissionSchedule(
+          emissionsPerSecond,
+          endTs,
+          bountyMillibps,
+          {
+            accounts: {
+              yiToken,
+              schedule: emissionSchedule,
+              reserveTokens: reserveTokens.address,
+              authority,
+              admin,
+              payer,
+              systemProgram: SystemProgram.programId,
+            },
+          }
+        ),
+      ]),
+    };
+  }
+
+  /**
+   * Accounts for cranking the emission schedule of a Yi token.
+   */
+  private async crankEmissionsAccounts({
+    yiTokenMint,
+    cranker,
+  }: {
+    yiTokenMint: PublicKey;
+    cranker: PublicKey;
+  }) {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [emissionSchedule] = await findEmissionScheduleAddress(yiToken);
+    const scheduleData = await this.programs.Yi.account.emissionSchedule.fetch(
+      emissionSchedule
+    );
+    const bountyTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.underlyingTokenMint,
+      owner: cranker,
+    });
+    return {
+      instruction: bountyTokens.instruction,
+      accounts: {
+        yiToken,
+        yiMint: yiTokenMint,
+        schedule: emissionSchedule,
+        reserveTokens: scheduleData.reserveTokens,
+        yiUnderlyingTokens: yiTokenData.underlyingTokens,
+        bountyTokens: bountyTokens.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+    };
+  }
+
+  /**
+   * Moves the emitted underlying tokens into the pool, paying the bounty to the cranker.
+   * @returns
+   */
+  async crankEmissions({
+    yiTokenMint,
+    cranker = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Owner of the account receiving the bounty.
+     */
+    cranker?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { instruction, accounts } = await this.crankEmissionsAccounts({
+      yiTokenMint,
+      cranker,
+    });
+    return this.provider.newTX([
+      instruction,
+      this.programs.Yi.instruction.crankEmissions({
+        accounts,
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Changes the emission rate and end time of the emission schedule of a Yi token.
+   * @returns
+   */
+  async setEmissionSchedule({
+    yiTokenMint,
+    emissionsPerSecond,
+    endTs,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    emissionsPerSecond: u64;
+    endTs: BN;
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { instruction, accounts } = await this.crankEmissionsAccounts({
+      yiTokenMint,
+      cranker: authority,
+    });
+    return this.provider.newTX([
+      instruction,
+      this.programs.Yi.instruction.setEmissionSchedule(
+        emissionsPerSecond,
+        endTs,
+        {
+          accounts: {
+            crank: accounts,
+            authority,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Registers a harvest adapter program for a Yi token.
+   * @returns
+   */
+  async registerHarvestAdapter({
+    yiTokenMint,
+    adapterProgram,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    adapterProgram: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    harvestAdapter: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [harvestAdapter] = await findHarvestAdapterAddress(
+      yiToken,
+      adapterProgram
+    );
+    return {
+      harvestAdapter,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.registerHarvestAdapter({
+          accounts: {
+            yiToken,
+            admin,
+            adapterProgram,
+            adapter: harvestAdapter,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Harvests yield from a registered harvest adapter.
+   * @returns
+   */
+  async harvest({
+    yiTokenMint,
+    adapterProgram,
+    adapterAccounts = [],
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    adapterProgram: PublicKey;
+    /**
+     * Accounts passed to the adapter after the Yi token's underlying tokens.
+     */
+    adapterAccounts?: AccountMeta[];
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [harvestAdapter] = await findHarvestAdapterAddress(
+      yiToken,
+      adapterProgram
+    );
+    return this.provider.newTX([
+      this.programs.Yi.instruction.harvest({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          adapter: harvestAdapter,
+          adapterProgram,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+        },
+        remainingAccounts: adapterAccounts,
+      }),
+    ]);
+  }
+
+  /**
+   * Registers the strategy of a Yi token.
+   * @returns
+   */
+  async registerStrategy({
+    yiTokenMint,
+    strategyProgram,
+    strategyTokens,
+    maxDeployedMillibps,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    strategyProgram: PublicKey;
+    /**
+     * Token account of the strategy receiving deployed underlying tokens, owned by
+     * the strategy authority of the strategy program.
+     */
+    strategyTokens: PublicKey;
+    /**
+     * Maximum share of the pool to deploy, in thousands of bps.
+     */
+    maxDeployedMillibps: number;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    strategy: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [strategy] = await findStrategyAddress(yiToken);
+    return {
+      strategy,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.registerStrategy(maxDeployedMillibps, {
+          accounts: {
+            yiToken,
+            admin,
+            strategyProgram,
+            strategyTokens,
+            strategy,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Sets the maximum share of the pool deployed to the strategy.
+   * @returns
+   */
+  async setStrategyRatio({
+    yiTokenMint,
+    maxDeployedMillibps,
+    admin = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    maxDeployedMillibps: number;
+    admin?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [strategy] = await findStrategyAddress(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.setStrategyRatio(maxDeployedMillibps, {
+        accounts: {
+          yiToken,
+          admin,
+          strategy,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Accounts of the strategy of a Yi token.
+   */
+  private async strategyAccounts(yiToken: PublicKey) {
+    const [strategy] = await findStrategyAddress(yiToken);
+    const strategyData = await this.programs.Yi.account.strategy.fetch(
+      strategy
+    );
+    return {
+      strategy,
+      strategyProgram: strategyData.program,
+      strategyTokens: strategyData.strategyTokens,
+    };
+  }
+
+  /**
+   * Deploys idle underlying tokens to, or recalls them from, the strategy.
+   * @returns
+   */
+  async rebalance({
+    yiTokenMint,
+    strategyAccounts = [],
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Accounts passed through to the strategy program.
+     */
+    strategyAccounts?: AccountMeta[];
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.rebalance({
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          strategy: await this.strategyAccounts(yiToken),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+        remainingAccounts: strategyAccounts,
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens, recalling funds from the strategy if the liquidity buffer is short.
+   * @returns
+   */
+  async unstakeWithRecall({
+    amount,
+    authority = this.provider.wallet.publicKey,
+    strategyAccounts = [],
+    rateProvider,
+  }: {
+    /**
+     * Yi Token Amount
+     */
+    amount: TokenAmount;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Accounts passed through to the strategy program.
+     */
+    strategyAccounts?: AccountMeta[];
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.unstakeWithRecall(amount.toU64(), {
+        accounts: {
+          unstake: {
+            yiToken,
+            yiMint: amount.token.mintAccount,
+            sourceYiTokens: authorityATAs.accounts.yi,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          strategy: await this.strategyAccounts(yiToken),
+        },
+        remainingAccounts: [
+          ...rateProviderAccountMetas(rateProvider),
+          ...strategyAccounts,
+        ],
+      }),
+    ]);
+  }
+
+  /**
+   * Registers a swap route for compounding tokens of another mint.
+   * @returns
+   */
+  async registerSwapRoute({
+    yiTokenMint,
+    inputMint,
+    swapProgram,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Mint of the tokens to swap into the underlying token.
+     */
+    inputMint: PublicKey;
+    swapProgram: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    swapRoute: PublicKey;
+    sideVault: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [swapRoute] = await findSwapRouteAddress(yiToken, inputMint);
+    const sideVault = await getOrCreateATA({
+      provider: this.provider,
+      mint: inputMint,
+      owner: swapRoute,
+    });
+    return {
+      swapRoute,
+      sideVault: sideVault.address,
+      tx: this.provider.newTX([
+        sideVault.instruction,
+        this.programs.Yi.instruction.registerSwapRoute({
+          accounts: {
+            yiToken,
+            admin,
+            inputMint,
+            swapProgram,
+            route: swapRoute,
+            sideVault: sideVault.address,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Swaps the side vault of a swap route into the underlying token and compounds it.
+   * @returns
+   */
+  async compoundFrom({
+    yiTokenMint,
+    inputMint,
+    minAmountOut,
+    swapAccounts = [],
+    admin = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Mint of the tokens to swap.
+     */
+    inputMint: PublicKey;
+    /**
+     * Minimum underlying tokens to receive from the swap.
+     */
+    minAmountOut: u64;
+    /**
+     * Accounts passed through to the swap program.
+     */
+    swapAccounts?: AccountMeta[];
+    admin?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [swapRoute] = await findSwapRouteAddress(yiToken, inputMint);
+    const routeData = await this.programs.Yi.account.swapRoute.fetch(swapRoute);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.compoundFrom(minAmountOut, {
+        accounts: {
+          yiToken,
+          yiMint: yiTokenMint,
+          route: swapRoute,
+          swapProgram: routeData.swapProgram,
+          sideVault: routeData.sideVault,
+          yiUnderlyingTokens: yiTokenData.underlyingTokens,
+          admin,
+        },
+        remainingAccounts: swapAccounts,
+      }),
+    ]);
+  }
+
+  /**
+   * Creates an escrow for locking Yi tokens in exchange for voting power.
+   * @returns
+   */
+  async createEscrow({
+    yiTokenMint,
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    escrow: PublicKey;
+    escrowTokens: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [escrow] = await findEscrowAddress(yiToken, owner);
+    const escrowTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenMint,
+      owner: escrow,
+    });
+    return {
+      escrow,
+      escrowTokens: escrowTokens.address,
+      tx: this.provider.newTX([
+        escrowTokens.instruction,
+        this.programs.Yi.instruction.createEscrow({
+          accounts: {
+            yiToken,
+            escrow,
+            escrowTokens: escrowTokens.address,
+            owner,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Locks Yi tokens in an escrow until `unlockAt`.
+   * @returns
+   */
+  async lock({
+    amount,
+    unlockAt,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Amount of Yi tokens to lock.
+     */
+    amount: TokenAmount;
+    /**
+     * Unix timestamp at which the tokens may be withdrawn.
+     */
+    unlockAt: BN;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const [escrow] = await findEscrowAddress(yiToken, owner);
+    const escrowData = await this.programs.Yi.account.escrow.fetch(escrow);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.lock(amount.toU64(), unlockAt, {
+        accounts: {
+          escrow,
+          escrowTokens: escrowData.tokens,
+          owner,
+          sourceYiTokens: await getATAAddress({
+            mint: amount.token.mintAccount,
+            owner,
+          }),
+          sourceAuthority: owner,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Extends the lock of an escrow to `unlockAt`.
+   * @returns
+   */
+  async extendLock({
+    yiTokenMint,
+    unlockAt,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Unix timestamp at which the tokens may be withdrawn.
+     */
+    unlockAt: BN;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [escrow] = await findEscrowAddress(yiToken, owner);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.extendLock(unlockAt, {
+        accounts: {
+          escrow,
+          owner,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Withdraws all Yi tokens from an escrow whose lock has expired.
+   * @returns
+   */
+  async exitEscrow({
+    yiTokenMint,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [escrow] = await findEscrowAddress(yiToken, owner);
+    const escrowData = await this.programs.Yi.account.escrow.fetch(escrow);
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenMint,
+      owner,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.exit({
+        accounts: {
+          escrow,
+          escrowTokens: escrowData.tokens,
+          owner,
+          destinationYiTokens: destination.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a registrar providing voter weight for an SPL Governance realm from deposited Yi tokens.
+   * @returns
+   */
+  async createRegistrar({
+    yiTokenMint,
+    realm,
+    governanceProgram,
+    governingTokenMint,
+    realmAuthority = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    realm: PublicKey;
+    governanceProgram: PublicKey;
+    /**
+     * Governing token mint of the realm whose voter weight is provided.
+     */
+    governingTokenMint: PublicKey;
+    realmAuthority?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{ tx: TransactionEnvelope; registrar: PublicKey }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [registrar] = await findRegistrarAddress(realm, governingTokenMint);
+    return {
+      registrar,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createRegistrar({
+          accounts: {
+            yiToken,
+            realm,
+            governanceProgram,
+            governingTokenMint,
+            realmAuthority,
+            registrar,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Creates a voter and its voter weight record.
+   * @returns
+   */
+  async createVoter({
+    registrar,
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    registrar: PublicKey;
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    voter: PublicKey;
+    voterWeightRecord: PublicKey;
+  }> {
+    const registrarData = await this.programs.Yi.account.registrar.fetch(
+      registrar
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      registrarData.yiToken
+    );
+    const [voter] = await findVoterAddress(registrar, owner);
+    const [voterWeightRecord] = await findVoterWeightRecordAddress(
+      registrar,
+      owner
+    );
+    const voterTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.mint,
+      owner: voter,
+    });
+    return {
+      voter,
+      voterWeightRecord,
+      tx: this.provider.newTX([
+        voterTokens.instruction,
+        this.programs.Yi.instruction.createVoter({
+          accounts: {
+            registrar,
+            yiToken: registrarData.yiToken,
+            voter,
+            voterWeightRecord,
+            voterTokens: voterTokens.address,
+            owner,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Deposits Yi tokens into a voter.
+   * @returns
+   */
+  async depositToVoter({
+    registrar,
+    amount,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    registrar: PublicKey;
+    /**
+     * Amount of Yi tokens to deposit.
+     */
+    amount: TokenAmount;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [voter] = await findVoterAddress(registrar, owner);
+    const voterData = await this.programs.Yi.account.voter.fetch(voter);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.depositToVoter(amount.toU64(), {
+        accounts: {
+          voter,
+          voterTokens: voterData.tokens,
+          sourceYiTokens: await getATAAddress({
+            mint: amount.token.mintAccount,
+            owner,
+          }),
+          sourceAuthority: owner,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Withdraws Yi tokens from a voter.
+   * @returns
+   */
+  async withdrawFromVoter({
+    registrar,
+    amount,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    registrar: PublicKey;
+    /**
+     * Amount of Yi tokens to withdraw.
+     */
+    amount: TokenAmount;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [voter] = await findVoterAddress(registrar, owner);
+    const [voterWeightRecord] = await findVoterWeightRecordAddress(
+      registrar,
+      owner
+    );
+    const registrarData = await this.programs.Yi.account.registrar.fetch(
+      registrar
+    );
+    const [tokenOwnerRecord] = await findTokenOwnerRecordAddress(
+      registrarData.governanceProgramId,
+      registrarData.realm,
+      registrarData.governingTokenMint,
+      owner
+    );
+    const voterData = await this.programs.Yi.account.voter.fetch(voter);
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: amount.token.mintAccount,
+      owner,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.withdrawFromVoter(amount.toU64(), {
+        accounts: {
+          registrar,
+          voter,
+          voterTokens: voterData.tokens,
+          voterWeightRecord,
+          owner,
+          tokenOwnerRecord,
+          destinationYiTokens: destination.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Writes the voter weight of a voter. Must be included in the same transaction as the governance
+   * instruction using the weight.
+   * @returns
+   */
+  async updateVoterWeightRecord({
+    registrar,
+    owner = this.provider.wallet.publicKey,
+  }: {
+    registrar: PublicKey;
+    owner?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const registrarData = await this.programs.Yi.account.registrar.fetch(
+      registrar
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      registrarData.yiToken
+    );
+    const [voter] = await findVoterAddress(registrar, owner);
+    const [voterWeightRecord] = await findVoterWeightRecordAddress(
+      registrar,
+      owner
+    );
+    const voterData = await this.programs.Yi.account.voter.fetch(voter);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.updateVoterWeightRecord({
+        accounts: {
+          registrar,
+          yiToken: registrarData.yiToken,
+          yiMint: yiTokenData.mint,
+          voter,
+          voterTokens: voterData.tokens,
+          voterWeightRecord,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Fetches a VoterWeightRecord.
+   * @returns
+   */
+  async fetchVoterWeightRecord(
+    voterWeightRecord: PublicKey
+  ): Promise<VoterWeightRecordData> {
+    const accountInfo = await this.provider.connection.getAccountInfo(
+      voterWeightRecord
+    );
+    if (
+      !accountInfo ||
+      !accountInfo.data
+        .slice(0, VOTER_WEIGHT_RECORD_DISCRIMINATOR.length)
+        .equals(VOTER_WEIGHT_RECORD_DISCRIMINATOR)
+    ) {
+      throw new Error(
+        `VoterWeightRecord ${voterWeightRecord.toString()} not found`
+      );
+    }
+    return this.programs.Yi.coder.types.decode<VoterWeightRecordData>(
+      "VoterWeightRecord",
+      accountInfo.data.slice(VOTER_WEIGHT_RECORD_DISCRIMINATOR.length)
+    );
+  }
+
+  /**
+   * Creates a term for splitting Yi tokens into principal and yield tokens.
+   * @returns
+   */
+  async createTerm({
+    yiTokenMint,
+    maturityTs,
+    principalMintKP = Keypair.generate(),
+    yieldMintKP = Keypair.generate(),
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Unix timestamp at which the term matures.
+     */
+    maturityTs: BN;
+    principalMintKP?: Signer;
+    yieldMintKP?: Signer;
+    payer?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    term: PublicKey;
+    principalMint: PublicKey;
+    yieldMint: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [term] = await findTermAddress(yiToken, maturityTs);
+    const yiMint = await this.provider.connection.getParsedAccountInfo(
+      yiTokenMint
+    );
+    const decimals = (
+      yiMint.value?.data as { parsed: { info: { decimals: number } } }
+    ).parsed.info.decimals;
+    const [initPrincipalMintTX, initYieldMintTX] = await Promise.all(
+      [principalMintKP, yieldMintKP].map((mintKP) =>
+        createInitMintInstructions({
+          provider: this.provider,
+          mintKP,
+          decimals,
+          mintAuthority: term,
+          freezeAuthority: term,
+        })
+      )
+    );
+    const termYiTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenMint,
+      owner: term,
+    });
+    return {
+      term,
+      principalMint: principalMintKP.publicKey,
+      yieldMint: yieldMintKP.publicKey,
+      tx: initPrincipalMintTX.combine(initYieldMintTX).combine(
+        this.provider.newTX([
+          termYiTokens.instruction,
+          this.programs.Yi.instruction.createTerm(maturityTs, {
+            accounts: {
+              yiToken,
+              yiMint: yiTokenMint,
+              term,
+              principalMint: principalMintKP.publicKey,
+              yieldMint: yieldMintKP.publicKey,
+              termYiTokens: termYiTokens.address,
+              payer,
+              systemProgram: SystemProgram.programId,
+            },
+          }),
+        ])
+      ),
+    };
+  }
+
+  /**
+   * Accounts of a term.
+   */
+  private async termAccounts(term: PublicKey) {
+    const termData = await this.programs.Yi.account.term.fetch(term);
+    return {
+      termData,
+      accounts: {
+        term,
+        termYiTokens: termData.yiTokens,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      },
+    };
+  }
+
+  /**
+   * Splits Yi tokens into principal and yield tokens.
+   * @returns
+   */
+  async split({
+    term,
+    amount,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    term: PublicKey;
+    /**
+     * Amount of Yi tokens to split.
+     */
+    amount: TokenAmount;
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { termData, accounts } = await this.termAccounts(term);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        principal: termData.principalMint,
+        yield: termData.yieldMint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.split(amount.toU64(), {
+        accounts: {
+          term: accounts,
+          principalMint: termData.principalMint,
+          yieldMint: termData.yieldMint,
+          sourceYiTokens: await getATAAddress({
+            mint: amount.token.mintAccount,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          destinationPrincipalTokens: authorityATAs.accounts.principal,
+          destinationYieldTokens: authorityATAs.accounts.yield,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Merges equal amounts of principal and yield tokens back into Yi tokens.
+   * @returns
+   */
+  async merge({
+    term,
+    amount,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    term: PublicKey;
+    /**
+     * Amount of principal and yield tokens to merge.
+     */
+    amount: u64;
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { termData, accounts } = await this.termAccounts(term);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      termData.yiToken
+    );
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        principal: termData.principalMint,
+        yield: termData.yieldMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      authorityATAs.createAccountInstructions.yi,
+      this.programs.Yi.instruction.merge(amount, {
+        accounts: {
+          term: accounts,
+          principalMint: termData.principalMint,
+          yieldMint: termData.yieldMint,
+          sourcePrincipalTokens: authorityATAs.accounts.principal,
+          sourceYieldTokens: authorityATAs.accounts.yield,
+          sourceAuthority: authority,
+          destinationYiTokens: authorityATAs.accounts.yi,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Redeems principal tokens of a matured term for Yi tokens.
+   * @returns
+   */
+  async redeemPrincipal({
+    term,
+    amount,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    term: PublicKey;
+    /**
+     * Amount of principal tokens to redeem.
+     */
+    amount: u64;
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { termData, accounts } = await this.termAccounts(term);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      termData.yiToken
+    );
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.mint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.redeemPrincipal(amount, {
+        accounts: {
+          term: accounts,
+          yiToken: termData.yiToken,
+          principalMint: termData.principalMint,
+          sourcePrincipalTokens: await getATAAddress({
+            mint: termData.principalMint,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          destinationYiTokens: destination.address,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Redeems yield tokens of a matured term for Yi tokens.
+   * @returns
+   */
+  async redeemYield({
+    term,
+    amount,
+    authority = this.provider.wallet.publicKey,
+  }: {
+    term: PublicKey;
+    /**
+     * Amount of yield tokens to redeem.
+     */
+    amount: u64;
+    authority?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const { termData, accounts } = await this.termAccounts(term);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      termData.yiToken
+    );
+    const destination = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.mint,
+      owner: authority,
+    });
+    return this.provider.newTX([
+      destination.instruction,
+      this.programs.Yi.instruction.redeemYield(amount, {
+        accounts: {
+          term: accounts,
+          yiToken: termData.yiToken,
+          yieldMint: termData.yieldMint,
+          sourceYieldTokens: await getATAAddress({
+            mint: termData.yieldMint,
+            owner: authority,
+          }),
+          sourceAuthority: authority,
+          destinationYiTokens: destination.address,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Fixes the maturity rate of a matured term. Anyone may call this.
+   * @returns
+   */
+  async settleTerm({ term }: { term: PublicKey }): Promise<TransactionEnvelope> {
+    const { termData, accounts } = await this.termAccounts(term);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.settleTerm({
+        accounts: {
+          term: accounts,
+          yiToken: termData.yiToken,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Stakes underlying tokens into a new position, minting a position token
+   * in place of Yi tokens.
+   * @returns
+   */
+  async stakeToPosition({
+    yiTokenMint,
+    amount,
+    positionMintKP = Keypair.generate(),
+    authority = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    /**
+     * Keypair of the mint of the position token.
+     */
+    positionMintKP?: Signer;
+    /**
+     * Authority staking tokens, which receives the position token.
+     */
+    authority?: PublicKey;
+    payer?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<{
+    tx: TransactionEnvelope;
+    position: PublicKey;
+    positionMint: PublicKey;
+  }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const [position] = await findPositionAddress(positionMintKP.publicKey);
+    const initMintTX = await createInitMintInstructions({
+      provider: this.provider,
+      mintKP: positionMintKP,
+      decimals: 0,
+      mintAuthority: position,
+      freezeAuthority: position,
+    });
+    const positionYiTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenMint,
+      owner: position,
+    });
+    const holderPositionTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: positionMintKP.publicKey,
+      owner: authority,
+    });
+    return {
+      position,
+      positionMint: positionMintKP.publicKey,
+      tx: initMintTX.combine(
+        this.provider.newTX([
+          positionYiTokens.instruction,
+          holderPositionTokens.instruction,
+          this.programs.Yi.instruction.stakeToPosition(amount, {
+            accounts: {
+              stake: {
+                yiToken,
+                yiMint: yiTokenMint,
+                sourceTokens: await getATAAddress({
+                  mint: yiTokenData.underlyingTokenMint,
+                  owner: authority,
+                }),
+                sourceAuthority: authority,
+                yiUnderlyingTokens: yiTokenData.underlyingTokens,
+                destinationYiTokens: positionYiTokens.address,
+                tokenProgram: TOKEN_PROGRAM_ID,
+              },
+              position,
+              positionMint: positionMintKP.publicKey,
+              destinationPositionTokens: holderPositionTokens.address,
+              payer,
+              systemProgram: SystemProgram.programId,
+            },
+            remainingAccounts: rateProviderAccountMetas(rateProvider),
+          }),
+        ])
+      ),
+    };
+  }
+
+  /**
+   * Redeems shares of a position for underlying tokens.
+   * @returns
+   */
+  async unstakeFromPosition({
+    position,
+    shares,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    position: PublicKey;
+    /**
+     * Number of shares to redeem, or ALL_TOKENS for all remaining shares.
+     */
+    shares: u64;
+    /**
+     * Holder of the position token.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const positionData = await this.programs.Yi.account.position.fetch(
+      position
+    );
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(
+      positionData.yiToken
+    );
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.unstakeFromPosition(shares, {
+        accounts: {
+          unstake: {
+            yiToken: positionData.yiToken,
+            yiMint: yiTokenData.mint,
+            sourceYiTokens: authorityATAs.accounts.yi,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          position,
+          positionYiTokens: positionData.tokens,
+          positionMint: positionData.mint,
+          holderPositionTokens: await getATAAddress({
+            mint: positionData.mint,
+            owner: authority,
+          }),
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a stake receipt recording stakes and unstakes of an owner.
+   * @returns
+   */
+  async createStakeReceipt({
+    yiTokenMint,
+    owner = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    owner?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{ tx: TransactionEnvelope; receipt: PublicKey }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [receipt] = await findStakeReceiptAddress(yiToken, owner);
+    return {
+      receipt,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createStakeReceipt({
+          accounts: {
+            yiToken,
+            receipt,
+            owner,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Stakes underlying tokens for Yi tokens, recording the stake on the stake receipt.
+   * @returns
+   */
+  async stakeWithReceipt({
+    yiTokenMint,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    /**
+     * Authority staking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [receipt] = await findStakeReceiptAddress(yiToken, authority);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      authorityATAs.createAccountInstructions.yi,
+      this.programs.Yi.instruction.stakeWithReceipt(amount, {
+        accounts: {
+          stake: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceTokens: authorityATAs.accounts.underlying,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationYiTokens: authorityATAs.accounts.yi,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          receipt,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens, recording the unstake on the stake receipt.
+   * @returns
+   */
+  async unstakeWithReceipt({
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Yi Token Amount
+     */
+    amount: TokenAmount;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const [receipt] = await findStakeReceiptAddress(yiToken, authority);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.unstakeWithReceipt(amount.toU64(), {
+        accounts: {
+          unstake: {
+            yiToken,
+            yiMint: amount.token.mintAccount,
+            sourceYiTokens: authorityATAs.accounts.yi,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          receipt,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a referral tracking stakes brought to a Yi token by a referrer.
+   * @returns
+   */
+  async createReferral({
+    yiTokenMint,
+    referrer,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    referrer: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{ tx: TransactionEnvelope; referral: PublicKey }> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [referral] = await findReferralAddress(yiToken, referrer);
+    return {
+      referral,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createReferral({
+          accounts: {
+            yiToken,
+            referral,
+            referrer,
+            admin,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Stakes underlying tokens on behalf of a referrer, who receives a share of the stake fee.
+   * @returns
+   */
+  async stakeWithReferral({
+    yiTokenMint,
+    amount,
+    referrer,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    referrer: PublicKey;
+    /**
+     * Authority staking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [referral] = await findReferralAddress(yiToken, referrer);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    const referrerTokens = await getOrCreateATA({
+      provider: this.provider,
+      mint: yiTokenData.underlyingTokenMint,
+      owner: referrer,
+    });
+    return this.provider.newTX([
+      authorityATAs.createAccountInstructions.yi,
+      referrerTokens.instruction,
+      this.programs.Yi.instruction.stakeWithReferral(amount, {
+        accounts: {
+          stake: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceTokens: authorityATAs.accounts.underlying,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationYiTokens: authorityATAs.accounts.yi,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          referral,
+          referrerTokens: referrerTokens.address,
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Sets the share of the stake fee paid to referrers.
+   * @returns
+   */
+  setReferralFeeShare({
+    yiToken,
+    referralFeeShareMillibps,
+    admin = this.provider.wallet.publicKey,
+  }: {
+    yiToken: PublicKey;
+    referralFeeShareMillibps: number;
+    admin?: PublicKey;
+  }): TransactionEnvelope {
+    return this.provider.newTX([
+      this.programs.Yi.instruction.setReferralFeeShare(
+        referralFeeShareMillibps,
+        {
+          accounts: {
+            yiToken,
+            admin,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Exempts an authority, which may be a program's PDA, from the fees of a Yi token.
+   * @returns
+   */
+  async createFeeExemption({
+    yiToken,
+    exemptKey,
+    admin = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    yiToken: PublicKey;
+    /**
+     * Authority or program to exempt.
+     */
+    exemptKey: PublicKey;
+    admin?: PublicKey;
+    payer?: PublicKey;
+  }): Promise<{ tx: TransactionEnvelope; feeExemption: PublicKey }> {
+    const [feeExemption] = await findFeeExemptionAddress(yiToken, exemptKey);
+    return {
+      feeExemption,
+      tx: this.provider.newTX([
+        this.programs.Yi.instruction.createFeeExemption(exemptKey, {
+          accounts: {
+            yiToken,
+            admin,
+            feeExemption,
+            payer,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Removes a fee exemption.
+   * @returns
+   */
+  async removeFeeExemption({
+    yiToken,
+    exemptKey,
+    admin = this.provider.wallet.publicKey,
+    rentRecipient = this.provider.wallet.publicKey,
+  }: {
+    yiToken: PublicKey;
+    exemptKey: PublicKey;
+    admin?: PublicKey;
+    rentRecipient?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [feeExemption] = await findFeeExemptionAddress(yiToken, exemptKey);
+    return this.provider.newTX([
+      this.programs.Yi.instruction.removeFeeExemption({
+        accounts: {
+          yiToken,
+          admin,
+          feeExemption,
+          rentRecipient,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Stakes underlying tokens without a stake fee.
+   * @returns
+   */
+  async stakeFeeExempt({
+    yiTokenMint,
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Mint of the Yi token.
+     */
+    yiTokenMint: PublicKey;
+    /**
+     * Amount of underlying tokens to stake.
+     */
+    amount: u64;
+    /**
+     * Authority staking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(yiTokenMint);
+    const [feeExemption] = await findFeeExemptionAddress(yiToken, authority);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      authorityATAs.createAccountInstructions.yi,
+      this.programs.Yi.instruction.stakeFeeExempt(amount, {
+        accounts: {
+          stake: {
+            yiToken,
+            yiMint: yiTokenMint,
+            sourceTokens: authorityATAs.accounts.underlying,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationYiTokens: authorityATAs.accounts.yi,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          exemption: { feeExemption },
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Unstakes Yi tokens without an unstake fee.
+   * @returns
+   */
+  async unstakeFeeExempt({
+    amount,
+    authority = this.provider.wallet.publicKey,
+    rateProvider,
+  }: {
+    /**
+     * Yi Token Amount
+     */
+    amount: TokenAmount;
+    /**
+     * Authority unstaking tokens.
+     */
+    authority?: PublicKey;
+    /**
+     * Rate provider to refresh, if any.
+     */
+    rateProvider?: PublicKey;
+  }): Promise<TransactionEnvelope> {
+    const [yiToken] = await findYiTokenAddress(amount.token.mintAccount);
+    const [feeExemption] = await findFeeExemptionAddress(yiToken, authority);
+    const yiTokenData = await this.programs.Yi.account.yiToken.fetch(yiToken);
+    const authorityATAs = await getOrCreateATAs({
+      provider: this.provider,
+      mints: {
+        underlying: yiTokenData.underlyingTokenMint,
+        yi: yiTokenData.mint,
+      },
+      owner: authority,
+    });
+    return this.provider.newTX([
+      ...authorityATAs.instructions,
+      this.programs.Yi.instruction.unstakeFeeExempt(amount.toU64(), {
+        accounts: {
+          unstake: {
+            yiToken,
+            yiMint: amount.token.mintAccount,
+            sourceYiTokens: authorityATAs.accounts.yi,
+            sourceAuthority: authority,
+            yiUnderlyingTokens: yiTokenData.underlyingTokens,
+            destinationUnderlyingTokens: authorityATAs.accounts.underlying,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
+          exemption: { feeExemption },
+        },
+        remainingAccounts: rateProviderAccountMetas(rateProvider),
+      }),
+    ]);
+  }
+
+  /**
+   * Limits the underlying tokens which may be unstaked from a Yi token per window.
+   * @returns
+   */
+  setOutflowLimit({
+    yiToken,
+    outflowLimit,
+    outflowWindowSeconds,
+    admin = this.provider.wallet.publicKey,
+  }: {
+    yiToken: PublicKey;
+    /**
+     * Maximum underlying tokens unstaked per window, or zero for no limit.
+     */
+    outflowLimit: u64;
+    outflowWindowSeconds: BN;
+    admin?: PublicKey;
+  }): TransactionEnvelope {
+    return this.provider.newTX([
+      this.programs.Yi.instruction.setOutflowLimit(
+        outflowLimit,
+        outflowWindowSeconds,
+        {
+          accounts: {
+            yiToken,
+            admin,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Configures the exchange rate circuit breaker of a Yi token, pausing or unpausing it.
+   * @returns
+   */
+  setCircuitBreaker({
+    yiToken,
+    rateJumpLimitMillibps,
+    isPaused = false,
+    admin = this.provider.wallet.publicKey,
+  }: {
+    yiToken: PublicKey;
+    /**
+     * Maximum rate change within a second, or zero to disable the circuit breaker.
+     */
+    rateJumpLimitMillibps: number;
+    isPaused?: boolean;
+    admin?: PublicKey;
+  }): TransactionEnvelope {
+    return this.provider.newTX([
+      this.programs.Yi.instruction.setCircuitBreaker(
+        rateJumpLimitMillibps,
+        isPaused,
+        {
+          accounts: {
+            yiToken,
+            admin,
+          },
+        }
+      ),
+    ]);
+  }
 }
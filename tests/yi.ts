@@ -1,5 +1,13 @@
-import { assertTXSuccess, chaiSolana } from "@saberhq/chai-solana";
+import { BN, utils } from "@project-serum/anchor";
 import {
+  assertTXSuccess,
+  chaiSolana,
+  expectTX,
+} from "@saberhq/chai-solana";
+import { sleep } from "@saberhq/solana-contrib";
+import {
+  getATAAddress,
+  getOrCreateATAs,
   getTokenAccount,
   SPLToken,
   Token,
@@ -8,12 +16,25 @@ import {
   TokenAugmentedProvider,
   u64,
 } from "@saberhq/token-utils";
-import { Keypair } from "@solana/web3.js";
+import type { AccountMeta } from "@solana/web3.js";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { expect } from "chai";
 import * as chai from "chai";
+import invariant from "tiny-invariant";
 
-import { findYiTokenAddress } from "../src/pda";
+import { ALL_TOKENS } from "../src/constants";
+import {
+  findStrategyAuthorityAddress,
+  findTokenOwnerRecordAddress,
+  findYiTokenAddress,
+} from "../src/pda";
 import type { YiTokenData } from "../src/programs/yi";
+import { YiErrors } from "../src/programs/yi";
 import { makeSDK } from "./workspace";
 
 chai.use(chaiSolana);
@@ -41,25 +62,560 @@ describe("YiToken", () => {
     expect(mint).to.eqAddress(yiMintKP.publicKey);
   });
 
+  it("stake and unstake SOL", async () => {
+    const wrappedSolToken = Token.fromMint(
+      new PublicKey("So11111111111111111111111111111111111111112"),
+      9
+    );
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken: wrappedSolToken,
+    });
+    await assertTXSuccess(createYiTokenTX);
+    const solYiToken = Token.fromMint(mint, 9);
+
+    await assertTXSuccess(
+      await sdk.stakeSol({
+        yiTokenMint: mint,
+        amount: new u64(1_000_000_000),
+      })
+    );
+    const stakedData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(stakedData.totalUnderlying).to.bignumber.eq(new u64(1_000_000_000));
+    const yiTokens = await getTokenAccount(
+      provider,
+      await getATAAddress({ mint, owner: provider.walletKey })
+    );
+    expect(yiTokens.amount).to.bignumber.eq(new u64(1_000_000_000));
+
+    const lamportsBefore = await provider.connection.getBalance(
+      provider.walletKey
+    );
+    await assertTXSuccess(
+      await sdk.unstakeSol({
+        amount: new TokenAmount(solYiToken, ALL_TOKENS),
+      })
+    );
+    const lamportsAfter = await provider.connection.getBalance(
+      provider.walletKey
+    );
+    // less the transaction fee
+    expect(lamportsAfter - lamportsBefore).to.be.greaterThan(999_000_000);
+
+    const unstakedData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(unstakedData.totalUnderlying).to.bignumber.eq(new u64(0));
+  });
+
+  it("fixed-maturity pool", async () => {
+    const underlyingToken = await provider.createToken();
+    const now = Math.floor(Date.now() / 1_000);
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      maturity: {
+        maturityTs: new BN(now + 6),
+        stakeDeadlineTs: new BN(now + 3),
+        // 10%
+        earlyExitPenaltyMillibps: 1_000_000,
+      },
+    });
+    await assertTXSuccess(createYiTokenTX);
+    const maturityYiToken = Token.fromMint(mint, underlyingToken.decimals);
+    const createdData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(createdData.maturityTs).to.bignumber.eq(new BN(now + 6));
+    expect(createdData.stakeDeadlineTs).to.bignumber.eq(new BN(now + 3));
+    expect(createdData.earlyExitPenaltyMillibps).to.eq(1_000_000);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(2_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+
+    // early exit forfeits 10% to the remaining holders
+    await assertTXSuccess(
+      await sdk.unstake({
+        amount: new TokenAmount(maturityYiToken, 500_000),
+      })
+    );
+    expect(
+      (await getTokenAccount(provider, underlyingATA.address)).amount
+    ).to.bignumber.eq(new u64(1_450_000));
+    const earlyData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(earlyData.totalUnderlying).to.bignumber.eq(new u64(550_000));
+
+    await sleep(4_000);
+    await expectTX(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) }),
+      "stake after deadline"
+    ).to.be.rejectedWith(`0x${YiErrors.StakingClosed.code.toString(16)}`);
+
+    await sleep(3_000);
+    await assertTXSuccess(
+      await sdk.unstake({
+        amount: new TokenAmount(maturityYiToken, 500_000),
+      })
+    );
+    expect(
+      (await getTokenAccount(provider, underlyingATA.address)).amount
+    ).to.bignumber.eq(new u64(2_000_000));
+  });
+
+  it("fixed-maturity pool without early exits", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      maturity: {
+        maturityTs: new BN(Math.floor(Date.now() / 1_000) + 60),
+      },
+    });
+    await assertTXSuccess(createYiTokenTX);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(1_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+    await expectTX(
+      await sdk.unstake({
+        amount: new TokenAmount(
+          Token.fromMint(mint, underlyingToken.decimals),
+          1_000_000
+        ),
+      }),
+      "unstake before maturity"
+    ).to.be.rejectedWith(`0x${YiErrors.PoolNotMatured.code.toString(16)}`);
+  });
+
+  it("referrals", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      // 10% stake fee
+      stakeFeeMillibps: 9_000_000,
+      admin: provider.walletKey,
+    });
+    await assertTXSuccess(createYiTokenTX);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(2_000_000)
+        ),
+      ])
+    );
+    // the first stake is 1:1 and takes no fee
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+    // 50% of the stake fee
+    await assertTXSuccess(
+      sdk.setReferralFeeShare({ yiToken, referralFeeShareMillibps: 5_000_000 })
+    );
+
+    const referrer = Keypair.generate().publicKey;
+    const notAdmin = Keypair.generate();
+    await expectTX(
+      (
+        await sdk.createReferral({
+          yiTokenMint: mint,
+          referrer,
+          admin: notAdmin.publicKey,
+        })
+      ).tx.addSigners(notAdmin),
+      "referral by a non-admin"
+    ).to.be.rejected;
+    const { tx: createReferralTX, referral } = await sdk.createReferral({
+      yiTokenMint: mint,
+      referrer,
+    });
+    await assertTXSuccess(createReferralTX);
+    await assertTXSuccess(
+      await sdk.stakeWithReferral({
+        yiTokenMint: mint,
+        amount: new u64(1_000_000),
+        referrer,
+      })
+    );
+
+    const referrerTokens = await getTokenAccount(
+      provider,
+      await getATAAddress({ mint: underlyingToken.mintAccount, owner: referrer })
+    );
+    expect(referrerTokens.amount).to.bignumber.eq(new u64(50_000));
+    const referralData = await sdk.programs.Yi.account.referral.fetch(referral);
+    expect(referralData.referredVolume).to.bignumber.eq(new u64(1_000_000));
+    expect(referralData.totalRebates).to.bignumber.eq(new u64(50_000));
+    const yiTokenData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(yiTokenData.totalUnderlying).to.bignumber.eq(new u64(1_950_000));
+    expect(yiTokenData.totalStakeFees).to.bignumber.eq(new u64(50_000));
+  });
+
+  it("fee exemptions", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      // 10% stake and unstake fees
+      stakeFeeMillibps: 9_000_000,
+      unstakeFeeMillibps: 9_000_000,
+      admin: provider.walletKey,
+    });
+    await assertTXSuccess(createYiTokenTX);
+    const feeYiToken = Token.fromMint(mint, underlyingToken.decimals);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(2_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+
+    const { tx: createExemptionTX } = await sdk.createFeeExemption({
+      yiToken,
+      exemptKey: provider.walletKey,
+    });
+    await assertTXSuccess(createExemptionTX);
+
+    await assertTXSuccess(
+      await sdk.stakeFeeExempt({
+        yiTokenMint: mint,
+        amount: new u64(1_000_000),
+      })
+    );
+    const yiATA = await getATAAddress({ mint, owner: provider.walletKey });
+    expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+      new u64(2_000_000)
+    );
+
+    await assertTXSuccess(
+      await sdk.unstakeFeeExempt({
+        amount: new TokenAmount(feeYiToken, 1_000_000),
+      })
+    );
+    expect(
+      (await getTokenAccount(provider, underlyingATA.address)).amount
+    ).to.bignumber.eq(new u64(1_000_000));
+
+    await assertTXSuccess(
+      await sdk.removeFeeExemption({ yiToken, exemptKey: provider.walletKey })
+    );
+    await expectTX(
+      await sdk.stakeFeeExempt({
+        yiTokenMint: mint,
+        amount: new u64(1_000_000),
+      }),
+      "stake without exemption"
+    ).to.be.rejected;
+  });
+
+  it("outflow limit", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      admin: provider.walletKey,
+    });
+    await assertTXSuccess(createYiTokenTX);
+    const limitedYiToken = Token.fromMint(mint, underlyingToken.decimals);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(1_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+    await assertTXSuccess(
+      sdk.setOutflowLimit({
+        yiToken,
+        outflowLimit: new u64(300_000),
+        outflowWindowSeconds: new BN(3_600),
+      })
+    );
+
+    await assertTXSuccess(
+      await sdk.unstake({ amount: new TokenAmount(limitedYiToken, 200_000) })
+    );
+    await expectTX(
+      await sdk.unstake({ amount: new TokenAmount(limitedYiToken, 200_000) }),
+      "unstake beyond the limit"
+    ).to.be.rejectedWith(
+      `0x${YiErrors.OutflowLimitExceeded.code.toString(16)}`
+    );
+    await assertTXSuccess(
+      await sdk.unstake({ amount: new TokenAmount(limitedYiToken, 100_000) })
+    );
+  });
+
+  it("sync without an admin", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+    });
+    await assertTXSuccess(createYiTokenTX);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(1_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+    await expectTX(
+      await sdk.sync({ yiTokenMint: mint }),
+      "sync without an admin"
+    ).to.be.rejectedWith(`0x${YiErrors.NoAdmin.code.toString(16)}`);
+  });
+
+  it("circuit breaker", async () => {
+    const underlyingToken = await provider.createToken();
+    const { mint, yiToken, tx: createYiTokenTX } = await sdk.createYiToken({
+      underlyingToken,
+      admin: provider.walletKey,
+    });
+    await assertTXSuccess(createYiTokenTX);
+
+    const underlyingATA = await provider.getOrCreateATA({
+      mint: underlyingToken.mintAccount,
+    });
+    await assertTXSuccess(
+      provider.newTX([
+        underlyingATA.instruction,
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingATA.address,
+          provider.walletKey,
+          [],
+          new u64(2_000_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(1_000_000) })
+    );
+    // 10%
+    await assertTXSuccess(
+      sdk.setCircuitBreaker({ yiToken, rateJumpLimitMillibps: 1_000_000 })
+    );
+
+    // tokens sent directly to the pool do not move the rate
+    const { underlyingTokens } = await sdk.programs.Yi.account.yiToken.fetch(
+      yiToken
+    );
+    await assertTXSuccess(
+      provider.newTX([
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          underlyingToken.mintAccount,
+          underlyingTokens,
+          provider.walletKey,
+          [],
+          new u64(500_000)
+        ),
+      ])
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(100_000) }),
+      "stake after a donation"
+    );
+
+    // rewards jump the rate by over 10%, pausing the pool
+    await assertTXSuccess(
+      await sdk.depositRewards({
+        yiTokenMint: mint,
+        amount: new u64(200_000),
+      }),
+      "deposit trips the circuit breaker"
+    );
+    const pausedData = await sdk.programs.Yi.account.yiToken.fetch(yiToken);
+    expect(pausedData.isPaused).to.eq(1);
+    expect(pausedData.totalUnderlying).to.bignumber.eq(new u64(1_300_000));
+
+    await expectTX(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(100_000) }),
+      "stake while paused"
+    ).to.be.rejectedWith(`0x${YiErrors.PoolPaused.code.toString(16)}`);
+
+    await assertTXSuccess(
+      sdk.setCircuitBreaker({ yiToken, rateJumpLimitMillibps: 0 })
+    );
+    await assertTXSuccess(
+      await sdk.stake({ yiTokenMint: mint, amount: new u64(100_000) })
+    );
+  });
+
   describe("feeless yiToken", () => {
     let yiToken: Token;
+    let yiTokenKey: PublicKey;
     let underlyingToken: Token;
     let yiTokenData: YiTokenData;
 
     beforeEach("create yiToken", async () => {
       underlyingToken = await provider.createToken();
-      const {
-        mint,
-        tx: createYiTokenTX,
-        yiToken: yiTokenKey,
-      } = await sdk.createYiToken({
-        underlyingToken,
-      });
+      const { mint, tx: createYiTokenTX, yiToken: key } =
+        await sdk.createYiToken({
+          underlyingToken,
+          admin: provider.walletKey,
+        });
       await assertTXSuccess(createYiTokenTX);
       yiToken = Token.fromMint(mint, 6);
+      yiTokenKey = key;
       yiTokenData = await sdk.programs.Yi.account.yiToken.fetch(yiTokenKey);
     });
 
+    /**
+     * The mock yield program.
+     */
+    const mockYieldProgram = new PublicKey(
+      "CBTUQ3J4v2BDaHsRSs7DWqb4gfmEx5uzx2tHXQ8KDRtM"
+    );
+
+    /**
+     * Creates the mock yield reserve for the underlying token.
+     */
+    const createMockYieldReserve = async () => {
+      const [reserveAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from("Reserve")],
+        mockYieldProgram
+      );
+      const reserve = await provider.getOrCreateATA({
+        mint: underlyingToken.mintAccount,
+        owner: reserveAuthority,
+      });
+      if (reserve.instruction) {
+        await assertTXSuccess(provider.newTX([reserve.instruction]));
+      }
+      return {
+        reserve: reserve.address,
+        accounts: [
+          { pubkey: reserve.address, isSigner: false, isWritable: true },
+          { pubkey: reserveAuthority, isSigner: false, isWritable: false },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        ],
+      };
+    };
+
+    /**
+     * Creates the mock strategy reserve of a YiToken, owned by the strategy authority.
+     */
+    const createMockStrategyReserve = async (yiTokenKey: PublicKey) => {
+      const [strategyAuthority] = await findStrategyAuthorityAddress(
+        yiTokenKey,
+        mockYieldProgram
+      );
+      const reserve = await provider.getOrCreateATA({
+        mint: underlyingToken.mintAccount,
+        owner: strategyAuthority,
+      });
+      if (reserve.instruction) {
+        await assertTXSuccess(provider.newTX([reserve.instruction]));
+      }
+      return {
+        reserve: reserve.address,
+        accounts: [
+          { pubkey: reserve.address, isSigner: false, isWritable: true },
+          { pubkey: yiTokenKey, isSigner: false, isWritable: false },
+          { pubkey: strategyAuthority, isSigner: false, isWritable: false },
+          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        ],
+      };
+    };
+
+    /**
+     * Mints underlying tokens to the wallet.
+     */
+    const fundUnderlying = async (amount: u64) => {
+      const to = await provider.getOrCreateATA({
+        mint: underlyingToken.mintAccount,
+      });
+      await assertTXSuccess(
+        provider.newTX([
+          to.instruction,
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            underlyingToken.mintAccount,
+            to.address,
+            provider.walletKey,
+            [],
+            amount
+          ),
+        ])
+      );
+      return to;
+    };
+
     it("stake/unstake", async () => {
       const mintAmount = new u64(1_000_000);
 
@@ -138,6 +694,183 @@ describe("YiToken", () => {
       }
     });
 
+    it("migrate stake", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(500_000),
+        })
+      );
+      const { mint: newMint, tx: createNewTX } = await sdk.createYiToken({
+        underlyingToken,
+      });
+      await assertTXSuccess(createNewTX);
+
+      await expectTX(
+        await sdk.migrateStake({
+          amount: new TokenAmount(yiToken, 500_000),
+          toYiTokenMint: newMint,
+          minAmountOut: new u64(500_001),
+        })
+      ).to.be.rejectedWith(
+        `0x${YiErrors.MigrationOutputTooLow.code.toString(16)}`
+      );
+
+      await assertTXSuccess(
+        await sdk.migrateStake({
+          amount: new TokenAmount(yiToken, 500_000),
+          toYiTokenMint: newMint,
+          minAmountOut: new u64(500_000),
+        })
+      );
+      const newYiTokens = await getTokenAccount(
+        provider,
+        await getATAAddress({ mint: newMint, owner: provider.walletKey })
+      );
+      expect(newYiTokens.amount).to.bignumber.eq(new u64(500_000));
+      const oldYiTokens = await getTokenAccount(
+        provider,
+        await getATAAddress({
+          mint: yiToken.mintAccount,
+          owner: provider.walletKey,
+        })
+      );
+      expect(oldYiTokens.amount).to.bignumber.eq(new u64(0));
+    });
+
+    it("batch stake/unstake", async () => {
+      const to = await fundUnderlying(new u64(1_000_000));
+      const other = Keypair.generate().publicKey;
+      const { accounts: yiATAs, instructions } = await getOrCreateATAs({
+        provider,
+        mints: { mine: yiToken.mintAccount },
+        owner: provider.walletKey,
+      });
+      const otherYi = await provider.getOrCreateATA({
+        mint: yiToken.mintAccount,
+        owner: other,
+      });
+      await assertTXSuccess(
+        provider.newTX([...instructions, otherYi.instruction])
+      );
+
+      await assertTXSuccess(
+        await sdk.batchStake({
+          yiTokenMint: yiToken.mintAccount,
+          entries: [
+            {
+              amount: new u64(100_000),
+              source: to.address,
+              destination: yiATAs.mine,
+            },
+            {
+              amount: new u64(300_000),
+              source: to.address,
+              destination: otherYi.address,
+            },
+          ],
+        })
+      );
+      expect(
+        (await getTokenAccount(provider, yiATAs.mine)).amount
+      ).to.bignumber.eq(new u64(100_000));
+      expect(
+        (await getTokenAccount(provider, otherYi.address)).amount
+      ).to.bignumber.eq(new u64(300_000));
+      expect(
+        (await getTokenAccount(provider, yiTokenData.underlyingTokens)).amount
+      ).to.bignumber.eq(new u64(400_000));
+
+      await assertTXSuccess(
+        await sdk.batchUnstake({
+          yiTokenMint: yiToken.mintAccount,
+          entries: [
+            {
+              amount: new u64(40_000),
+              source: yiATAs.mine,
+              destination: to.address,
+            },
+            {
+              amount: new u64(60_000),
+              source: yiATAs.mine,
+              destination: to.address,
+            },
+          ],
+        })
+      );
+      expect(
+        (await getTokenAccount(provider, to.address)).amount
+      ).to.bignumber.eq(new u64(700_000));
+    });
+
+    it("stake/unstake all", async () => {
+      const to = await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: ALL_TOKENS,
+        })
+      );
+      expect(
+        (await getTokenAccount(provider, to.address)).amount
+      ).to.bignumber.eq(new u64(0));
+      const yiATA = await getATAAddress({
+        mint: yiToken.mintAccount,
+        owner: provider.walletKey,
+      });
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(1_000_000)
+      );
+
+      await assertTXSuccess(
+        await sdk.unstakeAllAndClose({ yiTokenMint: yiToken.mintAccount })
+      );
+      expect(
+        (await getTokenAccount(provider, to.address)).amount
+      ).to.bignumber.eq(new u64(1_000_000));
+      expect(await provider.getAccountInfo(yiATA)).to.be.null;
+    });
+
+    it("stake/unstake creating token accounts", async () => {
+      const to = await fundUnderlying(new u64(1_000_000));
+      const yiATA = await getATAAddress({
+        mint: yiToken.mintAccount,
+        owner: provider.walletKey,
+      });
+      expect(await provider.getAccountInfo(yiATA)).to.be.null;
+
+      await assertTXSuccess(
+        await sdk.stakeToATA({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(200_000),
+        })
+      );
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(200_000)
+      );
+
+      // the account already exists the second time
+      await assertTXSuccess(
+        await sdk.stakeToATA({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(300_000)
+      );
+
+      await assertTXSuccess(
+        await sdk.unstakeToATA({
+          amount: new TokenAmount(yiToken, 300_000),
+        })
+      );
+      expect(
+        (await getTokenAccount(provider, to.address)).amount
+      ).to.bignumber.eq(new u64(1_000_000));
+    });
+
     it("compound direct tokens", async () => {
       const mintAmount = new u64(1_000_000);
       const compoundAmount = new u64(500_000);
@@ -216,6 +949,32 @@ describe("YiToken", () => {
           yiTokenData.underlyingTokens
         );
         expect(yiUnderlying.amount).to.bignumber.eq(new u64(700_000));
+
+        // direct transfers are not counted until synced
+        const yiTokenDataBeforeSync =
+          await sdk.programs.Yi.account.yiToken.fetch(yiTokenKey);
+        expect(yiTokenDataBeforeSync.totalUnderlying).to.bignumber.eq(
+          new u64(200_000)
+        );
+
+        const notAdmin = Keypair.generate();
+        await expectTX(
+          (
+            await sdk.sync({
+              yiTokenMint: yiToken.mintAccount,
+              admin: notAdmin.publicKey,
+            })
+          ).addSigners(notAdmin),
+          "sync by a non-admin"
+        ).to.be.rejected;
+        await assertTXSuccess(
+          await sdk.sync({ yiTokenMint: yiToken.mintAccount })
+        );
+        const yiTokenDataAfterSync =
+          await sdk.programs.Yi.account.yiToken.fetch(yiTokenKey);
+        expect(yiTokenDataAfterSync.totalUnderlying).to.bignumber.eq(
+          new u64(700_000)
+        );
       }
 
       // unstake half of tokens
@@ -256,5 +1015,1057 @@ describe("YiToken", () => {
         expect(yiUnderlying.amount).to.bignumber.eq(new u64(350_000 - 116_665));
       }
     });
+
+    it("deposit rewards", async () => {
+      const to = await fundUnderlying(new u64(1_000_000));
+
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(200_000),
+        })
+      );
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(500_000),
+        })
+      );
+
+      const yiTokenDataAfter = await sdk.programs.Yi.account.yiToken.fetch(
+        yiTokenKey
+      );
+      expect(yiTokenDataAfter.totalUnderlying).to.bignumber.eq(
+        new u64(700_000)
+      );
+
+      await assertTXSuccess(
+        await sdk.unstake({
+          amount: new TokenAmount(yiToken, 100_000),
+        })
+      );
+      const destination = await getTokenAccount(provider, to.address);
+      expect(destination.amount).to.bignumber.eq(new u64(650_000));
+
+      const stats = await sdk.programs.Yi.account.yiToken.fetch(yiTokenKey);
+      expect(stats.totalStaked).to.bignumber.eq(new u64(200_000));
+      expect(stats.totalUnstaked).to.bignumber.eq(new u64(350_000));
+      expect(stats.totalRewardsDeposited).to.bignumber.eq(new u64(500_000));
+      expect(stats.totalStakeFees).to.bignumber.eq(new u64(0));
+      expect(stats.totalUnstakeFees).to.bignumber.eq(new u64(0));
+      expect(stats.numOperations).to.bignumber.eq(new u64(2));
+    });
+
+    it("stake after an inflating reward deposit", async () => {
+      await fundUnderlying(new u64(3_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(1),
+        })
+      );
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(999_999),
+        })
+      );
+
+      await expectTX(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(999_999),
+        }),
+        "stake rounded down to nothing"
+      ).to.be.rejectedWith(`0x${YiErrors.NothingMinted.code.toString(16)}`);
+      await expectTX(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(1_000_000),
+          minAmountOut: new u64(2),
+        }),
+        "stake below the minimum amount out"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.StakeOutputTooLow.code.toString(16)}`
+      );
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(1_000_000),
+          minAmountOut: new u64(1),
+        })
+      );
+    });
+
+    it("rate oracle", async () => {
+      const { rateOracle, tx: createRateOracleTX } =
+        await sdk.createRateOracle({
+          yiTokenMint: yiToken.mintAccount,
+        });
+      await assertTXSuccess(createRateOracleTX);
+
+      const oracleData = await sdk.programs.Yi.account.rateOracle.fetch(
+        rateOracle
+      );
+      expect(oracleData.yiToken).to.eqAddress(yiTokenKey);
+      expect(oracleData.numObservations).to.bignumber.eq(new u64(1));
+
+      const yiTokenDataAfter = await sdk.programs.Yi.account.yiToken.fetch(
+        yiTokenKey
+      );
+      expect(yiTokenDataAfter.lastRate).to.bignumber.eq(
+        new u64("1000000000000")
+      );
+
+      await sleep(2_000);
+      await assertTXSuccess(
+        await sdk.recordRateObservation({
+          yiTokenMint: yiToken.mintAccount,
+        })
+      );
+      const oracleDataAfter = await sdk.programs.Yi.account.rateOracle.fetch(
+        rateOracle
+      );
+      expect(oracleDataAfter.numObservations).to.bignumber.eq(new u64(2));
+      expect(oracleDataAfter.latestIndex).to.bignumber.eq(new u64(1));
+    });
+
+    it("rate provider", async () => {
+      const { rateProvider, tx: createRateProviderTX } =
+        await sdk.createRateProvider({
+          yiTokenMint: yiToken.mintAccount,
+        });
+      await assertTXSuccess(createRateProviderTX);
+
+      const providerData = await sdk.programs.Yi.account.rateProvider.fetch(
+        rateProvider
+      );
+      expect(providerData.yiToken).to.eqAddress(yiTokenKey);
+      expect(providerData.rate).to.bignumber.eq(new u64("1000000000000"));
+
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(200_000),
+          rateProvider,
+        })
+      );
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(500_000),
+        })
+      );
+
+      // not refreshed by the deposit
+      const providerDataBefore =
+        await sdk.programs.Yi.account.rateProvider.fetch(rateProvider);
+      expect(providerDataBefore.rate).to.bignumber.eq(new u64("1000000000000"));
+
+      await assertTXSuccess(
+        await sdk.refreshRate({ yiTokenMint: yiToken.mintAccount })
+      );
+      const providerDataAfter = await sdk.programs.Yi.account.rateProvider.fetch(
+        rateProvider
+      );
+      expect(providerDataAfter.rate).to.bignumber.eq(new u64("3500000000000"));
+    });
+
+    it("flash loan", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(800_000),
+        })
+      );
+
+      const flashLoanTX = await sdk.flashLoan({
+        yiTokenMint: yiToken.mintAccount,
+        amount: new u64(500_000),
+      });
+      await assertTXSuccess(flashLoanTX);
+
+      // fee of 0.09% is compounded
+      const yiTokenDataAfter = await sdk.programs.Yi.account.yiToken.fetch(
+        yiTokenKey
+      );
+      expect(yiTokenDataAfter.totalUnderlying).to.bignumber.eq(
+        new u64(800_450)
+      );
+      expect(yiTokenDataAfter.flashLoanAmount).to.bignumber.eq(new u64(0));
+      const yiUnderlying = await getTokenAccount(
+        provider,
+        yiTokenData.underlyingTokens
+      );
+      expect(yiUnderlying.amount).to.bignumber.eq(new u64(800_450));
+    });
+
+    it("flash loan must be repaid", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(800_000),
+        })
+      );
+
+      const flashLoanTX = await sdk.flashLoan({
+        yiTokenMint: yiToken.mintAccount,
+        amount: new u64(500_000),
+      });
+      const [loanIx] = flashLoanTX.instructions;
+      invariant(loanIx, "loan instruction");
+      await expectTX(
+        provider.newTX([loanIx]),
+        "no repayment"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.FlashLoanNotRepaid.code.toString(16)}`
+      );
+    });
+
+    it("flash loan must be positive", async () => {
+      const flashLoanTX = await sdk.flashLoan({
+        yiTokenMint: yiToken.mintAccount,
+        amount: new u64(0),
+      });
+      await expectTX(flashLoanTX, "zero loan").to.be.rejectedWith(
+        `0x${YiErrors.ZeroAmount.code.toString(16)}`
+      );
+    });
+
+    it("reward stream", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      const rewardToken = await provider.createToken();
+      const notAdmin = Keypair.generate();
+      await expectTX(
+        (
+          await sdk.createRewardStream({
+            yiTokenMint: yiToken.mintAccount,
+            rewardMint: rewardToken.mintAccount,
+            admin: notAdmin.publicKey,
+          })
+        ).tx.addSigners(notAdmin),
+        "reward stream by a non-admin"
+      ).to.be.rejected;
+      const {
+        rewardStream,
+        rewardTokens,
+        tx: createRewardStreamTX,
+      } = await sdk.createRewardStream({
+        yiTokenMint: yiToken.mintAccount,
+        rewardMint: rewardToken.mintAccount,
+      });
+      await assertTXSuccess(createRewardStreamTX);
+      await assertTXSuccess(
+        provider.newTX([
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            rewardToken.mintAccount,
+            rewardTokens,
+            provider.walletKey,
+            [],
+            new u64(1_000_000_000)
+          ),
+        ])
+      );
+      await assertTXSuccess(
+        sdk.setRewardRate({
+          rewardStream,
+          rewardsPerSecond: new u64(1_000),
+        })
+      );
+
+      const { rewardPosition, tx: createRewardPositionTX } =
+        await sdk.createRewardPosition({ rewardStream });
+      await assertTXSuccess(createRewardPositionTX);
+      await assertTXSuccess(
+        await sdk.stakeWithRewards({
+          rewardStream,
+          amount: new u64(500_000),
+        })
+      );
+
+      const positionData = await sdk.programs.Yi.account.rewardPosition.fetch(
+        rewardPosition
+      );
+      expect(positionData.shares).to.bignumber.eq(new u64(500_000));
+
+      await sleep(2_000);
+      await assertTXSuccess(
+        await sdk.unstakeWithRewards({
+          rewardStream,
+          amount: new u64(500_000),
+        })
+      );
+      await assertTXSuccess(await sdk.claimRewards({ rewardStream }));
+
+      // the sole holder earns the entire stream while staked
+      const positionDataAfter =
+        await sdk.programs.Yi.account.rewardPosition.fetch(rewardPosition);
+      expect(positionDataAfter.shares).to.bignumber.eq(new u64(0));
+      expect(positionDataAfter.rewardsEarned).to.bignumber.eq(new u64(0));
+      const claimed = await getTokenAccount(
+        provider,
+        await getATAAddress({
+          mint: rewardToken.mintAccount,
+          owner: provider.walletKey,
+        })
+      );
+      expect(claimed.amount).to.bignumber.gt(new u64(0));
+      expect(claimed.amount).to.bignumber.lte(new u64(1_000_000_000));
+    });
+
+    it("emission schedule", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+
+      const cranker = Keypair.generate();
+      const now = Math.floor(Date.now() / 1_000);
+      const notAdmin = Keypair.generate();
+      await expectTX(
+        (
+          await sdk.createEmissionSchedule({
+            yiTokenMint: yiToken.mintAccount,
+            emissionsPerSecond: new u64(1_000),
+            endTs: new BN(now + 3_600),
+            admin: notAdmin.publicKey,
+          })
+        ).tx.addSigners(notAdmin),
+        "emission schedule by a non-admin"
+      ).to.be.rejected;
+      const { reserveTokens, tx: createEmissionScheduleTX } =
+        await sdk.createEmissionSchedule({
+          yiTokenMint: yiToken.mintAccount,
+          emissionsPerSecond: new u64(1_000),
+          endTs: new BN(now + 3_600),
+          bountyMillibps: 100_000,
+        });
+      await assertTXSuccess(createEmissionScheduleTX);
+      await assertTXSuccess(
+        provider.newTX([
+          SPLToken.createTransferInstruction(
+            TOKEN_PROGRAM_ID,
+            await getATAAddress({
+              mint: underlyingToken.mintAccount,
+              owner: provider.walletKey,
+            }),
+            reserveTokens,
+            provider.walletKey,
+            [],
+            new u64(500_000)
+          ),
+        ])
+      );
+
+      await sleep(2_000);
+      await assertTXSuccess(
+        await sdk.crankEmissions({
+          yiTokenMint: yiToken.mintAccount,
+          cranker: cranker.publicKey,
+        })
+      );
+
+      const yiTokenDataAfter = await sdk.programs.Yi.account.yiToken.fetch(
+        yiTokenKey
+      );
+      const compounded = yiTokenDataAfter.totalRewardsDeposited;
+      expect(compounded).to.bignumber.gt(new u64(0));
+      expect(yiTokenDataAfter.totalUnderlying).to.bignumber.eq(
+        new u64(100_000).add(compounded)
+      );
+
+      // the cranker earns 1% of the emission
+      const bounty = await getTokenAccount(
+        provider,
+        await getATAAddress({
+          mint: underlyingToken.mintAccount,
+          owner: cranker.publicKey,
+        })
+      );
+      expect(bounty.amount).to.bignumber.eq(compounded.div(new u64(99)));
+    });
+
+    it("harvest adapter", async () => {
+      const { mint, tx: createYiTokenTX } = await sdk.createYiToken({
+        underlyingToken,
+        admin: provider.walletKey,
+      });
+      await assertTXSuccess(createYiTokenTX);
+      const adminYiToken = Token.fromMint(mint, 6);
+
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: adminYiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+
+      const { harvestAdapter, tx: registerTX } =
+        await sdk.registerHarvestAdapter({
+          yiTokenMint: adminYiToken.mintAccount,
+          adapterProgram: mockYieldProgram,
+        });
+      await assertTXSuccess(registerTX);
+
+      // fund the mock yield source
+      const { reserve, accounts } = await createMockYieldReserve();
+      await assertTXSuccess(
+        provider.newTX([
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            underlyingToken.mintAccount,
+            reserve,
+            provider.walletKey,
+            [],
+            new u64(25_000)
+          ),
+        ])
+      );
+
+      await assertTXSuccess(
+        await sdk.harvest({
+          yiTokenMint: adminYiToken.mintAccount,
+          adapterProgram: mockYieldProgram,
+          adapterAccounts: accounts,
+        })
+      );
+
+      const [adminYiTokenKey] = await findYiTokenAddress(mint);
+      const adminYiTokenData = await sdk.programs.Yi.account.yiToken.fetch(
+        adminYiTokenKey
+      );
+      expect(adminYiTokenData.totalUnderlying).to.bignumber.eq(
+        new u64(125_000)
+      );
+      expect(adminYiTokenData.totalRewardsDeposited).to.bignumber.eq(
+        new u64(25_000)
+      );
+      const adapterData = await sdk.programs.Yi.account.harvestAdapter.fetch(
+        harvestAdapter
+      );
+      expect(adapterData.totalHarvested).to.bignumber.eq(new u64(25_000));
+    });
+
+    it("strategy with liquidity buffer", async () => {
+      const { mint, tx: createYiTokenTX } = await sdk.createYiToken({
+        underlyingToken,
+        admin: provider.walletKey,
+      });
+      await assertTXSuccess(createYiTokenTX);
+      const adminYiToken = Token.fromMint(mint, 6);
+      const [adminYiTokenKey] = await findYiTokenAddress(mint);
+
+      const to = await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: adminYiToken.mintAccount,
+          amount: new u64(1_000_000),
+        })
+      );
+
+      // strategy tokens must be owned by the strategy authority
+      const { reserve: harvestReserve } = await createMockYieldReserve();
+      await expectTX(
+        (
+          await sdk.registerStrategy({
+            yiTokenMint: adminYiToken.mintAccount,
+            strategyProgram: mockYieldProgram,
+            strategyTokens: harvestReserve,
+            maxDeployedMillibps: 8_000_000,
+          })
+        ).tx,
+        "strategy tokens owned by another account"
+      ).to.be.rejected;
+
+      const { reserve, accounts } = await createMockStrategyReserve(
+        adminYiTokenKey
+      );
+      // a liquidity buffer must stay in the vault
+      await expectTX(
+        (
+          await sdk.registerStrategy({
+            yiTokenMint: adminYiToken.mintAccount,
+            strategyProgram: mockYieldProgram,
+            strategyTokens: reserve,
+            maxDeployedMillibps: 10_000_000,
+          })
+        ).tx,
+        "deploy everything"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.InvalidStrategyRatio.code.toString(16)}`
+      );
+      const { tx: registerTX } = await sdk.registerStrategy({
+        yiTokenMint: adminYiToken.mintAccount,
+        strategyProgram: mockYieldProgram,
+        strategyTokens: reserve,
+        maxDeployedMillibps: 8_000_000,
+      });
+      await assertTXSuccess(registerTX);
+
+      await assertTXSuccess(
+        await sdk.rebalance({
+          yiTokenMint: adminYiToken.mintAccount,
+          strategyAccounts: accounts,
+        })
+      );
+      const deployedData = await sdk.programs.Yi.account.yiToken.fetch(
+        adminYiTokenKey
+      );
+      expect(deployedData.strategyDeployed).to.bignumber.eq(new u64(800_000));
+      expect(deployedData.totalUnderlying).to.bignumber.eq(new u64(1_000_000));
+
+      // the buffer cannot cover this unstake
+      await expectTX(
+        await sdk.unstake({
+          amount: new TokenAmount(adminYiToken, 500_000),
+        }),
+        "buffer is short"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.InsufficientLiquidity.code.toString(16)}`
+      );
+
+      await assertTXSuccess(
+        await sdk.unstakeWithRecall({
+          amount: new TokenAmount(adminYiToken, 500_000),
+          strategyAccounts: accounts,
+        })
+      );
+      const recalledData = await sdk.programs.Yi.account.yiToken.fetch(
+        adminYiTokenKey
+      );
+      expect(recalledData.strategyDeployed).to.bignumber.eq(new u64(500_000));
+      expect(recalledData.totalUnderlying).to.bignumber.eq(new u64(500_000));
+      const destination = await getTokenAccount(provider, to.address);
+      expect(destination.amount).to.bignumber.eq(new u64(500_000));
+
+      // rebalancing recalls the excess
+      await assertTXSuccess(
+        await sdk.rebalance({
+          yiTokenMint: adminYiToken.mintAccount,
+          strategyAccounts: accounts,
+        })
+      );
+      const rebalancedData = await sdk.programs.Yi.account.yiToken.fetch(
+        adminYiTokenKey
+      );
+      expect(rebalancedData.strategyDeployed).to.bignumber.eq(new u64(400_000));
+    });
+
+    it("compound from another mint", async () => {
+      const mockSwapProgram = new PublicKey(
+        "8p658PNUSea7Qpq3tGpWtNS5Lgb11PvSwQU82rRvX8ae"
+      );
+      const { mint, tx: createYiTokenTX } = await sdk.createYiToken({
+        underlyingToken,
+        admin: provider.walletKey,
+      });
+      await assertTXSuccess(createYiTokenTX);
+      const [adminYiTokenKey] = await findYiTokenAddress(mint);
+
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: mint,
+          amount: new u64(100_000),
+        })
+      );
+
+      const revenueToken = await provider.createToken();
+      const { sideVault, tx: registerTX } = await sdk.registerSwapRoute({
+        yiTokenMint: mint,
+        inputMint: revenueToken.mintAccount,
+        swapProgram: mockSwapProgram,
+      });
+      await assertTXSuccess(registerTX);
+
+      // set up the swap reserves
+      const [reserveAuthority] = await PublicKey.findProgramAddress(
+        [Buffer.from("Reserve")],
+        mockSwapProgram
+      );
+      const { accounts: reserves, instructions: createReservesIXs } =
+        await getOrCreateATAs({
+          provider,
+          mints: {
+            input: revenueToken.mintAccount,
+            output: underlyingToken.mintAccount,
+          },
+          owner: reserveAuthority,
+        });
+      await assertTXSuccess(
+        provider.newTX([
+          ...createReservesIXs,
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            underlyingToken.mintAccount,
+            reserves.output,
+            provider.walletKey,
+            [],
+            new u64(1_000_000)
+          ),
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            revenueToken.mintAccount,
+            sideVault,
+            provider.walletKey,
+            [],
+            new u64(100_000)
+          ),
+        ])
+      );
+
+      const swapAccounts = [
+        { pubkey: reserves.input, isSigner: false, isWritable: true },
+        { pubkey: reserves.output, isSigner: false, isWritable: true },
+        { pubkey: reserveAuthority, isSigner: false, isWritable: false },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      ];
+      const notAdmin = Keypair.generate();
+      await expectTX(
+        (
+          await sdk.compoundFrom({
+            yiTokenMint: mint,
+            inputMint: revenueToken.mintAccount,
+            minAmountOut: new u64(0),
+            swapAccounts,
+            admin: notAdmin.publicKey,
+          })
+        ).addSigners(notAdmin),
+        "compound by a non-admin"
+      ).to.be.rejected;
+      await assertTXSuccess(
+        await sdk.compoundFrom({
+          yiTokenMint: mint,
+          inputMint: revenueToken.mintAccount,
+          minAmountOut: new u64(50_000),
+          swapAccounts,
+        })
+      );
+
+      const yiTokenDataAfter = await sdk.programs.Yi.account.yiToken.fetch(
+        adminYiTokenKey
+      );
+      expect(yiTokenDataAfter.totalUnderlying).to.bignumber.eq(
+        new u64(150_000)
+      );
+      const sideVaultAfter = await getTokenAccount(provider, sideVault);
+      expect(sideVaultAfter.amount).to.bignumber.eq(new u64(0));
+    });
+
+    it("vote escrow", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+      const { tx: createEscrowTX, escrow, escrowTokens } =
+        await sdk.createEscrow({ yiTokenMint: yiToken.mintAccount });
+      await assertTXSuccess(createEscrowTX);
+
+      const now = Math.floor(Date.now() / 1_000);
+      await expectTX(
+        await sdk.lock({
+          amount: new TokenAmount(yiToken, 100_000),
+          unlockAt: new BN(now + 5 * 365 * 24 * 60 * 60),
+        }),
+        "lock too long"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.EscrowLockTooLong.code.toString(16)}`
+      );
+
+      await assertTXSuccess(
+        await sdk.lock({
+          amount: new TokenAmount(yiToken, 100_000),
+          unlockAt: new BN(now + 3),
+        })
+      );
+      const escrowData = await sdk.programs.Yi.account.escrow.fetch(escrow);
+      expect(escrowData.amount).to.bignumber.eq(new u64(100_000));
+      expect(escrowData.unlockAt).to.bignumber.eq(new BN(now + 3));
+      expect(
+        (await getTokenAccount(provider, escrowTokens)).amount
+      ).to.bignumber.eq(new u64(100_000));
+
+      await expectTX(
+        await sdk.extendLock({
+          yiTokenMint: yiToken.mintAccount,
+          unlockAt: new BN(now + 2),
+        }),
+        "shorten lock"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.EscrowLockTooShort.code.toString(16)}`
+      );
+      await expectTX(
+        await sdk.exitEscrow({ yiTokenMint: yiToken.mintAccount }),
+        "exit while locked"
+      ).to.be.rejectedWith(`0x${YiErrors.EscrowLocked.code.toString(16)}`);
+
+      await assertTXSuccess(
+        await sdk.extendLock({
+          yiTokenMint: yiToken.mintAccount,
+          unlockAt: new BN(now + 6),
+        })
+      );
+      await sleep(8_000);
+      await assertTXSuccess(
+        await sdk.exitEscrow({ yiTokenMint: yiToken.mintAccount })
+      );
+      expect(
+        (
+          await getTokenAccount(
+            provider,
+            await getATAAddress({
+              mint: yiToken.mintAccount,
+              owner: provider.walletKey,
+            })
+          )
+        ).amount
+      ).to.bignumber.eq(new u64(100_000));
+    });
+
+    it("voter weight", async () => {
+      const mockGovernanceProgram = new PublicKey(
+        "ZJ1LZAZjZc3tvLckZwvUJo6j4Gx14SPEmhAE1PxG1Fy"
+      );
+      const mockGovernanceIX = (
+        name: string,
+        data: Buffer,
+        keys: AccountMeta[]
+      ): TransactionInstruction =>
+        new TransactionInstruction({
+          programId: mockGovernanceProgram,
+          keys,
+          data: Buffer.concat([
+            Buffer.from(utils.sha256.hash(`global:${name}`), "hex").slice(0, 8),
+            data,
+          ]),
+        });
+      const writeIX = (account: PublicKey, data: Buffer) => {
+        const len = Buffer.alloc(4);
+        len.writeUInt32LE(data.length);
+        return mockGovernanceIX("write", Buffer.concat([len, data]), [
+          { pubkey: account, isSigner: false, isWritable: true },
+        ]);
+      };
+
+      // a RealmV2 with no council whose authority is the wallet
+      const communityMint = await provider.createToken();
+      const realmData = Buffer.concat([
+        Buffer.from([16]),
+        communityMint.mintAccount.toBuffer(),
+        Buffer.alloc(2 + 6 + 8 + 9 + 1 + 6 + 2),
+        Buffer.from([1]),
+        provider.walletKey.toBuffer(),
+      ]);
+      const realmKP = Keypair.generate();
+      await assertTXSuccess(
+        provider
+          .newTX([
+            SystemProgram.createAccount({
+              fromPubkey: provider.walletKey,
+              newAccountPubkey: realmKP.publicKey,
+              lamports:
+                await provider.connection.getMinimumBalanceForRentExemption(
+                  realmData.length
+                ),
+              space: realmData.length,
+              programId: mockGovernanceProgram,
+            }),
+            writeIX(realmKP.publicKey, realmData),
+          ])
+          .addSigners(realmKP)
+      );
+
+      const { registrar, tx: createRegistrarTX } = await sdk.createRegistrar({
+        yiTokenMint: yiToken.mintAccount,
+        realm: realmKP.publicKey,
+        governanceProgram: mockGovernanceProgram,
+        governingTokenMint: communityMint.mintAccount,
+      });
+      await assertTXSuccess(createRegistrarTX);
+      const { voterWeightRecord, tx: createVoterTX } = await sdk.createVoter({
+        registrar,
+      });
+      await assertTXSuccess(createVoterTX);
+
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+      await assertTXSuccess(
+        await sdk.depositToVoter({
+          registrar,
+          amount: new TokenAmount(yiToken, 100_000),
+        })
+      );
+      await assertTXSuccess(await sdk.updateVoterWeightRecord({ registrar }));
+      const recordData = await sdk.fetchVoterWeightRecord(voterWeightRecord);
+      expect(recordData.voterWeight).to.bignumber.eq(new u64(100_000));
+      expect(recordData.governingTokenOwner).to.eqAddress(provider.walletKey);
+
+      // a TokenOwnerRecordV2 with one unrelinquished vote
+      const [tokenOwnerRecord] = await findTokenOwnerRecordAddress(
+        mockGovernanceProgram,
+        realmKP.publicKey,
+        communityMint.mintAccount,
+        provider.walletKey
+      );
+      const tokenOwnerRecordData = (unrelinquishedVotesCount: number) => {
+        const counts = Buffer.alloc(8 + 4);
+        counts.writeUInt32LE(unrelinquishedVotesCount, 8);
+        return Buffer.concat([
+          Buffer.from([17]),
+          realmKP.publicKey.toBuffer(),
+          communityMint.mintAccount.toBuffer(),
+          provider.walletKey.toBuffer(),
+          counts,
+        ]);
+      };
+      const space = Buffer.alloc(8);
+      space.writeUInt32LE(tokenOwnerRecordData(0).length);
+      await assertTXSuccess(
+        provider.newTX([
+          mockGovernanceIX("create_token_owner_record", space, [
+            { pubkey: realmKP.publicKey, isSigner: false, isWritable: false },
+            {
+              pubkey: communityMint.mintAccount,
+              isSigner: false,
+              isWritable: false,
+            },
+            { pubkey: provider.walletKey, isSigner: false, isWritable: false },
+            { pubkey: tokenOwnerRecord, isSigner: false, isWritable: true },
+            { pubkey: provider.walletKey, isSigner: true, isWritable: true },
+            {
+              pubkey: SystemProgram.programId,
+              isSigner: false,
+              isWritable: false,
+            },
+          ]),
+          writeIX(tokenOwnerRecord, tokenOwnerRecordData(1)),
+        ])
+      );
+
+      // the tokens cannot be withdrawn and counted again while the vote stands
+      await expectTX(
+        await sdk.withdrawFromVoter({
+          registrar,
+          amount: new TokenAmount(yiToken, 100_000),
+        }),
+        "withdraw with an active vote"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.VoterHasActiveVotes.code.toString(16)}`
+      );
+
+      await assertTXSuccess(
+        provider.newTX([writeIX(tokenOwnerRecord, tokenOwnerRecordData(0))])
+      );
+      await assertTXSuccess(
+        await sdk.withdrawFromVoter({
+          registrar,
+          amount: new TokenAmount(yiToken, 100_000),
+        })
+      );
+      const recordDataAfter = await sdk.fetchVoterWeightRecord(
+        voterWeightRecord
+      );
+      expect(recordDataAfter.voterWeight).to.bignumber.eq(new u64(0));
+    });
+
+    it("principal and yield tokens", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      await assertTXSuccess(
+        await sdk.stake({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(400_000),
+        })
+      );
+      const yiATA = await getATAAddress({
+        mint: yiToken.mintAccount,
+        owner: provider.walletKey,
+      });
+
+      const maturityTs = new BN(Math.floor(Date.now() / 1_000) + 4);
+      const {
+        tx: createTermTX,
+        term,
+        principalMint,
+        yieldMint,
+      } = await sdk.createTerm({
+        yiTokenMint: yiToken.mintAccount,
+        maturityTs,
+      });
+      await assertTXSuccess(createTermTX);
+
+      await assertTXSuccess(
+        await sdk.split({ term, amount: new TokenAmount(yiToken, 200_000) })
+      );
+      const principalATA = await getATAAddress({
+        mint: principalMint,
+        owner: provider.walletKey,
+      });
+      const yieldATA = await getATAAddress({
+        mint: yieldMint,
+        owner: provider.walletKey,
+      });
+      expect(
+        (await getTokenAccount(provider, principalATA)).amount
+      ).to.bignumber.eq(new u64(200_000));
+      expect(
+        (await getTokenAccount(provider, yieldATA)).amount
+      ).to.bignumber.eq(new u64(200_000));
+
+      await assertTXSuccess(
+        await sdk.merge({ term, amount: new u64(50_000) })
+      );
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(250_000)
+      );
+
+      await expectTX(
+        await sdk.redeemPrincipal({ term, amount: new u64(150_000) }),
+        "redeem before maturity"
+      ).to.be.rejectedWith(`0x${YiErrors.TermNotMatured.code.toString(16)}`);
+
+      // double the exchange rate
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(400_000),
+        })
+      );
+      await sleep(6_000);
+
+      // yield compounded after settlement does not go to the term
+      await assertTXSuccess(await sdk.settleTerm({ term }));
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(100_000),
+        })
+      );
+
+      await assertTXSuccess(
+        await sdk.redeemPrincipal({ term, amount: new u64(150_000) })
+      );
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(325_000)
+      );
+      await assertTXSuccess(
+        await sdk.redeemYield({ term, amount: new u64(150_000) })
+      );
+      expect((await getTokenAccount(provider, yiATA)).amount).to.bignumber.eq(
+        new u64(400_000)
+      );
+    });
+
+    it("position tokens", async () => {
+      const underlying = await fundUnderlying(new u64(1_000_000));
+      const { tx, position, positionMint } = await sdk.stakeToPosition({
+        yiTokenMint: yiToken.mintAccount,
+        amount: new u64(400_000),
+      });
+      await assertTXSuccess(tx);
+      const positionATA = await getATAAddress({
+        mint: positionMint,
+        owner: provider.walletKey,
+      });
+      expect(
+        (await getTokenAccount(provider, positionATA)).amount
+      ).to.bignumber.eq(new u64(1));
+
+      const positionData = await sdk.programs.Yi.account.position.fetch(
+        position
+      );
+      expect(positionData.shares).to.bignumber.eq(new u64(400_000));
+      expect(positionData.costBasis).to.bignumber.eq(new u64(400_000));
+      expect(positionData.entryRate).to.bignumber.eq(yiTokenData.lastRate);
+
+      // double the exchange rate
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(400_000),
+        })
+      );
+
+      await assertTXSuccess(
+        await sdk.unstakeFromPosition({ position, shares: new u64(100_000) })
+      );
+      expect(
+        (await getTokenAccount(provider, underlying.address)).amount
+      ).to.bignumber.eq(new u64(400_000));
+      const partialData = await sdk.programs.Yi.account.position.fetch(
+        position
+      );
+      expect(partialData.shares).to.bignumber.eq(new u64(300_000));
+      expect(partialData.costBasis).to.bignumber.eq(new u64(300_000));
+
+      await assertTXSuccess(
+        await sdk.unstakeFromPosition({ position, shares: ALL_TOKENS })
+      );
+      expect(
+        (await getTokenAccount(provider, underlying.address)).amount
+      ).to.bignumber.eq(new u64(1_000_000));
+      expect(
+        (await getTokenAccount(provider, positionATA)).amount
+      ).to.bignumber.eq(new u64(0));
+    });
+
+    it("stake receipts", async () => {
+      await fundUnderlying(new u64(1_000_000));
+      const { tx, receipt } = await sdk.createStakeReceipt({
+        yiTokenMint: yiToken.mintAccount,
+      });
+      await assertTXSuccess(tx);
+
+      await assertTXSuccess(
+        await sdk.stakeWithReceipt({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(400_000),
+        })
+      );
+      // double the exchange rate
+      await assertTXSuccess(
+        await sdk.depositRewards({
+          yiTokenMint: yiToken.mintAccount,
+          amount: new u64(400_000),
+        })
+      );
+      await assertTXSuccess(
+        await sdk.unstakeWithReceipt({
+          amount: new TokenAmount(yiToken, 100_000),
+        })
+      );
+
+      const receiptData = await sdk.programs.Yi.account.stakeReceipt.fetch(
+        receipt
+      );
+      expect(receiptData.totalDeposited).to.bignumber.eq(new u64(400_000));
+      expect(receiptData.totalWithdrawn).to.bignumber.eq(new u64(200_000));
+      expect(receiptData.yiTokens).to.bignumber.eq(new u64(300_000));
+      expect(receiptData.costBasis).to.bignumber.eq(new u64(300_000));
+
+      await expectTX(
+        await sdk.unstakeWithReceipt({
+          amount: new TokenAmount(yiToken, 300_001),
+        }),
+        "unstake more than the receipt holds"
+      ).to.be.rejectedWith(
+        `0x${YiErrors.ReceiptBalanceExceeded.code.toString(16)}`
+      );
+    });
   });
 });